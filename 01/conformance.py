@@ -0,0 +1,88 @@
+#!/usr/bin/env python
+"""
+Feeds generated inputs to both this Python solution and the Rust one, and
+reports any input on which they disagree.
+
+Usage (from the repository root, after `cargo build`):
+    python 01/conformance.py [--cases N] [--seed N] [--max-size N] [--aoc PATH]
+
+Inputs come from `aoc generate 01`, and the Rust answers from
+`aoc run 01 --format json`. Both Python solvers are checked against the Rust
+answer, so keep --max-size small enough for solve_part2_naive.
+"""
+
+import argparse
+import json
+import subprocess
+import sys
+
+from solve import solve_part1
+from solve import solve_part2
+from solve import solve_part2_naive
+
+
+def soln_to_json(soln):
+    if soln is None:
+        return None
+    value, i, j, k = soln
+    return {"value": value, "i": i, "j": j, "k": k}
+
+
+def run_rust(aoc, text):
+    """
+    Returns the Rust answers for the given input as {part: answer}.
+    """
+    process = subprocess.Popen([aoc, "run", "01", "--input", "-", "--format", "json"],
+                               stdin=subprocess.PIPE, stdout=subprocess.PIPE)
+    output, _ = process.communicate(text)
+    if process.returncode != 0:
+        raise RuntimeError("aoc run failed on:\n" + text)
+    answers = {}
+    for line in output.splitlines():
+        record = json.loads(line)
+        answers[record["part"]] = record["answer"]
+    return answers
+
+
+def check_case(aoc, seed, size):
+    """
+    Returns a list of disagreements for one generated input.
+    """
+    text = subprocess.check_output([aoc, "generate", "01", "--seed", str(seed),
+                                    "--size", str(size)])
+    numbers = [int(line) for line in text.splitlines()]
+    rust = run_rust(aoc, text)
+    python = [
+        (1, "solve_part1", solve_part1(numbers)),
+        (2, "solve_part2_naive", soln_to_json(solve_part2_naive(numbers))),
+        (2, "solve_part2", soln_to_json(solve_part2(numbers))),
+    ]
+    problems = []
+    for part, name, answer in python:
+        if answer != rust.get(part):
+            problems.append("seed %d size %d: %s returned %s, Rust returned %s" % (
+                seed, size, name, json.dumps(answer), json.dumps(rust.get(part))))
+    return problems
+
+
+def main():
+    parser = argparse.ArgumentParser()
+    parser.add_argument("--cases", type=int, default=200)
+    parser.add_argument("--seed", type=int, default=0)
+    parser.add_argument("--max-size", type=int, default=20)
+    parser.add_argument("--aoc", default="target/debug/aoc")
+    args = parser.parse_args()
+
+    num_failures = 0
+    for case in xrange(args.cases):
+        seed = args.seed + case
+        size = 1 + case % args.max_size
+        for problem in check_case(args.aoc, seed, size):
+            print problem
+            num_failures += 1
+    print "%d cases, %d disagreements" % (args.cases, num_failures)
+    sys.exit(1 if num_failures else 0)
+
+
+if __name__ == "__main__":
+    main()
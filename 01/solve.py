@@ -1,8 +1,38 @@
 #!/usr/bin/env python
 
 from collections import defaultdict
+import re
 import sys
 
+# An optional sign followed by digits, e.g. "+1", "-2" or "3".
+NUMBER_RE = re.compile(r"^[+-]?[0-9]+$")
+
+
+def parse_input(text):
+    """
+    Parses signed integers separated by newlines, commas and/or whitespace, as
+    in the puzzle's "+1, -2, +3, +1". Raises ValueError for an empty token (as
+    in "1,,2" or a trailing comma), a sign that could belong to either of two
+    numbers (as in "1-2" or "+ 1"), or anything else that is not a number.
+    """
+    numbers = []
+    for line_number, line in enumerate(text.splitlines(), 1):
+        if not line.strip():
+            continue
+        fields = line.split(",")
+        for field in fields:
+            tokens = field.split()
+            if not tokens and len(fields) > 1:
+                raise ValueError("line %d: empty token in %r" % (line_number, line))
+            for token in tokens:
+                if NUMBER_RE.match(token):
+                    numbers.append(int(token))
+                elif token.lstrip("+-") == "" or "+" in token[1:] or "-" in token[1:]:
+                    raise ValueError("line %d: ambiguous sign: %r" % (line_number, token))
+                else:
+                    raise ValueError("line %d: not a number: %r" % (line_number, token))
+    return numbers
+
 
 def solve_part1(numbers):
     return sum(numbers)
@@ -70,52 +100,54 @@ def solve_part2(numbers):
     seq_sum = sum(numbers)
     # print "seq_sum:", seq_sum
 
-    # Case 1: We have k = 0. Get the solution in O(n).
-    if seq_sum == 0:
-        cumsum_to_i = {0: -1}
-        cumsum = 0
-        for j, number in enumerate(numbers):
-            cumsum += number
-            if cumsum in cumsum_to_i:
-                return (cumsum, cumsum_to_i[cumsum], j, 0)
-            cumsum_to_i[cumsum] = j
-        assert False, "should not reach here"
-
-    # Case 2: We have k > 0. Get the solution in O(n log n).
-    else:
-        # Group the cumsum values by their module when divided by seq_sum.
-        mod_to_indices = defaultdict(list)
-        cumsum = 0
-        cumsums = []
-        for j, number in enumerate(numbers):
-            cumsum += number
-            cumsums.append(cumsum)
-            mod_to_indices[cumsum % seq_sum].append(j)
-
-        # If i and j form a solution, they must be in the same modulo group.
-        best_soln = None
-        for mod, indices in mod_to_indices.iteritems():
-            indices.sort(key=lambda i: cumsums[i] / seq_sum)
-            # print mod, indices
-            for t in xrange(1, len(indices)):
-                j = indices[t - 1]
-                i = indices[t]
-                div_j = cumsums[j] / seq_sum
-                div_i = cumsums[i] / seq_sum
-                assert div_j <= div_i
-                k = div_i - div_j
-                soln = (cumsums[i], i, j, k)
-                # print "candidate:", soln
-                if soln_less_than(soln, best_soln):
-                    best_soln = soln
-        # print "final:", best_soln
-        return best_soln
+    # Case 1: We have k = 0. Get the solution in O(n). This always happens when
+    # seq_sum == 0, and can happen for any seq_sum.
+    cumsum_to_i = {0: -1}
+    cumsum = 0
+    for j, number in enumerate(numbers):
+        cumsum += number
+        if cumsum in cumsum_to_i:
+            return (cumsum, cumsum_to_i[cumsum], j, 0)
+        cumsum_to_i[cumsum] = j
+    assert seq_sum != 0, "should not reach here"
+
+    # Case 2: We have k > 0. Get the solution in O(n log n). All the cumsums
+    # seen in the first pass are distinct, so the multiples within a modulo
+    # group are distinct too.
+    # Group the cumsum values by their module when divided by seq_sum. The
+    # starting frequency 0 counts as index -1.
+    i_to_cumsum = dict((i, cumsum) for cumsum, i in cumsum_to_i.iteritems())
+    mod_to_indices = defaultdict(list)
+    for i, cumsum in i_to_cumsum.iteritems():
+        mod_to_indices[cumsum % seq_sum].append(i)
+
+    # If i and j form a solution, they must be in the same modulo group.
+    best_soln = None
+    for mod, indices in mod_to_indices.iteritems():
+        indices.sort(key=lambda i: i_to_cumsum[i] // seq_sum)
+        # print mod, indices
+        for t in xrange(1, len(indices)):
+            j = indices[t - 1]
+            i = indices[t]
+            # Starting from index -1 after k passes is the same place as the
+            # end of pass k - 1, which the last index already covers; and with
+            # k = 1 it is no repeat at all.
+            if j == -1:
+                continue
+            div_j = i_to_cumsum[j] // seq_sum
+            div_i = i_to_cumsum[i] // seq_sum
+            assert div_j < div_i
+            k = div_i - div_j
+            soln = (i_to_cumsum[i], i, j, k)
+            # print "candidate:", soln
+            if soln_less_than(soln, best_soln):
+                best_soln = soln
+    # print "final:", best_soln
+    return best_soln
 
 
 if __name__ == "__main__":
-    numbers = []
-    for line in sys.stdin:
-        numbers.append(int(line))
+    numbers = parse_input(sys.stdin.read())
     print "part 1:", solve_part1(numbers)
     print "part 2 naive:", solve_part2_naive(numbers)
     print "part 2 fast:", solve_part2(numbers)
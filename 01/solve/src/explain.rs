@@ -0,0 +1,135 @@
+// A step-by-step derivation of the part 2 answer from solve_part2_fast, so that it can be checked
+// by hand: the prefix sums at i and j, their shared modulo group and multiples, and the
+// frequencies around the repeat. When there is no answer, says why not.
+
+use std::fmt::Write;
+
+use super::Frequency;
+use super::IndexInfo;
+use super::Overflow;
+use super::add;
+use super::div_mod_euclid;
+use super::group_by_modulo;
+use super::mul;
+use super::solve_part1;
+use super::solve_part2_fast;
+
+// How many indices on each side of j to show in the trace.
+const TRACE_RADIUS: usize = 3;
+// How many indices of the modulo group to list.
+const MAX_LISTED: usize = 10;
+
+fn describe_index(index: i32) -> String {
+    if index == -1 { "the start".to_string() } else { format!("index {}", index) }
+}
+
+// Lists the indices of a modulo group, ordered by multiple, e.g. "-1 (multiple 0), 4 (1)".
+fn describe_group<T: Frequency>(index_infos: &[IndexInfo<T>]) -> String {
+    let mut index_infos = index_infos.to_vec();
+    index_infos.sort_unstable_by_key(|info| (info.multiple, info.index));
+    let mut listed: Vec<String> = index_infos.iter().take(MAX_LISTED)
+        .map(|info| format!("{} (multiple {})", info.index, info.multiple))
+        .collect();
+    if index_infos.len() > MAX_LISTED {
+        listed.push(format!("and {} more", index_infos.len() - MAX_LISTED));
+    }
+    listed.join(", ")
+}
+
+pub fn explain_part2<T: Frequency>(numbers: &[T]) -> Result<String, Overflow> {
+    let total = solve_part1(numbers)?;
+    let mut sums: Vec<T> = Vec::new();
+    let mut sum = T::ZERO;
+    for &number in numbers {
+        sum = add(sum, number)?;
+        sums.push(sum);
+    }
+    let sum_at = |index: i32| if index == -1 { T::ZERO } else { sums[index as usize] };
+
+    let mut text = String::new();
+    writeln!(text, "total (drift per pass): {}", total).unwrap();
+    let soln = match solve_part2_fast(numbers)? {
+        Some(soln) => soln,
+        None => {
+            // There is always an answer when total == 0, since the last prefix sum is then 0.
+            let (_sums, mut mod_to_index_infos) = group_by_modulo(numbers, total)?;
+            mod_to_index_infos.entry(T::ZERO).or_default()
+                .push(IndexInfo{index: -1, multiple: T::ZERO, modulo: T::ZERO});
+            writeln!(text, "no answer: no frequency is ever reached twice").unwrap();
+            writeln!(text, "a frequency can only come back on a later pass as an earlier prefix \
+                            sum plus a multiple of {}, so two prefix sums would have to share a \
+                            modulo class mod {}", total, total).unwrap();
+            // The last prefix sum is the total itself, so it always shares the start's class.
+            writeln!(text, "the {} prefix sums (counting the start) fall into {} classes; the \
+                            only shared one is the start's, with the last index, which is just \
+                            where the next pass starts",
+                     numbers.len() + 1, mod_to_index_infos.len()).unwrap();
+            return Ok(text);
+        },
+    };
+
+    writeln!(text, "answer: frequency {} is first reached at {} on pass 0, and again at index {} \
+                    on pass {}", soln.value, describe_index(soln.i), soln.j, soln.k).unwrap();
+    writeln!(text, "prefix sum at i = {}: {}", soln.i, sum_at(soln.i)).unwrap();
+    writeln!(text, "prefix sum at j = {}: {}", soln.j, sum_at(soln.j)).unwrap();
+    if total == T::ZERO {
+        writeln!(text, "the total is 0, so every pass repeats the first, and the repeat happens \
+                        on pass 0 where the two prefix sums are equal.").unwrap();
+    } else {
+        let (multiple_i, modulo) = div_mod_euclid(sum_at(soln.i), total)?;
+        let (multiple_j, _) = div_mod_euclid(sum_at(soln.j), total)?;
+        let (_sums, mut mod_to_index_infos) = group_by_modulo(numbers, total)?;
+        mod_to_index_infos.entry(T::ZERO).or_default()
+            .push(IndexInfo{index: -1, multiple: T::ZERO, modulo: T::ZERO});
+        writeln!(text, "  {} = {} * {} + {}", sum_at(soln.i), multiple_i, total, modulo).unwrap();
+        writeln!(text, "  {} = {} * {} + {}", sum_at(soln.j), multiple_j, total, modulo).unwrap();
+        writeln!(text, "both are in modulo group {} (mod {}), which holds indices {}",
+                 modulo, total, describe_group(&mod_to_index_infos[&modulo])).unwrap();
+        writeln!(text, "k = {} - {} = {} passes, and {} + {} * {} = {}", multiple_i, multiple_j,
+                 soln.k, sum_at(soln.j), soln.k, total, soln.value).unwrap();
+    }
+
+    writeln!(text, "trace of pass {}:", soln.k).unwrap();
+    let j = soln.j as usize;
+    let shift = mul(soln.k, total)?;
+    for index in j.saturating_sub(TRACE_RADIUS)..numbers.len().min(j + TRACE_RADIUS + 1) {
+        write!(text, "  index {}: {:+} -> {}", index, numbers[index], add(sums[index], shift)?)
+            .unwrap();
+        if index == j {
+            write!(text, "  <- repeat").unwrap();
+        }
+        writeln!(text).unwrap();
+    }
+    Ok(text)
+}
+
+#[test]
+fn test_explain_part2() {
+    assert_eq!(explain_part2(&[3, 3, 4, -2, -4]).unwrap(), "\
+total (drift per pass): 4
+answer: frequency 10 is first reached at index 2 on pass 0, and again at index 1 on pass 1
+prefix sum at i = 2: 10
+prefix sum at j = 1: 6
+  10 = 2 * 4 + 2
+  6 = 1 * 4 + 2
+both are in modulo group 2 (mod 4), which holds indices 1 (multiple 1), 2 (multiple 2)
+k = 2 - 1 = 1 passes, and 6 + 1 * 4 = 10
+trace of pass 1:
+  index 0: +3 -> 7
+  index 1: +3 -> 10  <- repeat
+  index 2: +4 -> 14
+  index 3: -2 -> 12
+  index 4: -4 -> 8
+");
+    assert!(explain_part2(&[1, -1]).unwrap().contains(
+        "frequency 0 is first reached at the start on pass 0, and again at index 1 on pass 0"));
+    assert_eq!(explain_part2(&[2, 2, 2, 7, -3]).unwrap(), "\
+total (drift per pass): 10
+no answer: no frequency is ever reached twice
+a frequency can only come back on a later pass as an earlier prefix sum plus a multiple of 10, so \
+two prefix sums would have to share a modulo class mod 10
+the 6 prefix sums (counting the start) fall into 5 classes; the only shared one is the start's, \
+with the last index, which is just where the next pass starts
+");
+    assert_eq!(explain_part2(&[i32::MAX, 1]), Err(Overflow));
+}
@@ -0,0 +1,125 @@
+// Answers "when is frequency F first reached?" for many targets against one input, starting from
+// any frequency.
+//
+// Starting from `start`, frequency F is reached at index j on pass k iff
+// sums[j] + k * total == F - start. With total != 0, that means sums[j] must be in the same
+// modulo group as F - start, with a multiple no bigger than its multiple, and the earliest pass
+// comes from the biggest such multiple. So after grouping and sorting once, each query is a binary
+// search.
+
+use std::collections::HashMap;
+
+use super::Frequency;
+use super::Overflow;
+use super::add;
+use super::div_mod_euclid;
+use super::group_by_modulo;
+use super::solve_part1;
+use super::sub;
+
+// Interpretation: the frequency is reached at index j on pass k (or at the start, if j == -1).
+#[derive(Clone, Debug, PartialEq)]
+pub struct Visit<T> {
+    pub j: i32,
+    pub k: T,
+}
+
+pub struct FirstVisits<T> {
+    start: T,
+    total: T,
+    // With total == 0: the first index at which each prefix sum is reached.
+    sum_to_index: HashMap<T, i32>,
+    // With total != 0: for each modulo group, (multiple, first index with that multiple), sorted
+    // by multiple.
+    mod_to_multiples: HashMap<T, Vec<(T, i32)>>,
+}
+
+impl<T: Frequency> FirstVisits<T> {
+    // Preprocesses the input in O(n log n).
+    pub fn new(numbers: &[T], start: T) -> Result<FirstVisits<T>, Overflow> {
+        let total = solve_part1(numbers)?;
+        let mut sum_to_index: HashMap<T, i32> = HashMap::new();
+        let mut mod_to_multiples: HashMap<T, Vec<(T, i32)>> = HashMap::new();
+        if total == T::ZERO {
+            let mut sum = T::ZERO;
+            for (j, &number) in numbers.iter().enumerate() {
+                sum = add(sum, number)?;
+                sum_to_index.entry(sum).or_insert(j as i32);
+            }
+        } else {
+            let (_sums, mod_to_index_infos) = group_by_modulo(numbers, total)?;
+            for (modulo, index_infos) in mod_to_index_infos {
+                let mut multiples: Vec<(T, i32)> = index_infos.iter()
+                    .map(|info| (info.multiple, info.index))
+                    .collect();
+                // Of the indices with the same multiple, only the first can be the answer.
+                multiples.sort_unstable();
+                multiples.dedup_by_key(|&mut (multiple, _)| multiple);
+                mod_to_multiples.insert(modulo, multiples);
+            }
+        }
+        Ok(FirstVisits{start, total, sum_to_index, mod_to_multiples})
+    }
+
+    // Returns where `target` is first reached, or None if it never is, in O(log n).
+    pub fn first_visit(&self, target: T) -> Result<Option<Visit<T>>, Overflow> {
+        let offset = sub(target, self.start)?;
+        if offset == T::ZERO {
+            return Ok(Some(Visit{j: -1, k: T::ZERO}));
+        }
+        if self.total == T::ZERO {
+            return Ok(self.sum_to_index.get(&offset).map(|&j| Visit{j, k: T::ZERO}));
+        }
+        let (multiple, modulo) = div_mod_euclid(offset, self.total)?;
+        let multiples = match self.mod_to_multiples.get(&modulo) {
+            Some(multiples) => multiples,
+            None => return Ok(None),
+        };
+        // The number of multiples <= multiple; the last of them is the one we want.
+        let num_reachable = multiples.partition_point(|&(m, _)| m <= multiple);
+        if num_reachable == 0 {
+            return Ok(None);
+        }
+        let (latest_multiple, j) = multiples[num_reachable - 1];
+        Ok(Some(Visit{j, k: sub(multiple, latest_multiple)?}))
+    }
+}
+
+#[test]
+fn test_first_visit() {
+    // Frequencies from 0: 3 6 10 8 4 | 7 10 14 12 8 | 11 14 18 16 12 | ...
+    let visits = FirstVisits::new(&[3, 3, 4, -2, -4], 0).unwrap();
+    assert_eq!(visits.first_visit(0), Ok(Some(Visit{j: -1, k: 0})));
+    assert_eq!(visits.first_visit(8), Ok(Some(Visit{j: 3, k: 0})));
+    assert_eq!(visits.first_visit(7), Ok(Some(Visit{j: 0, k: 1})));
+    assert_eq!(visits.first_visit(14), Ok(Some(Visit{j: 2, k: 1})));
+    assert_eq!(visits.first_visit(4003), Ok(Some(Visit{j: 0, k: 1000})));
+    assert_eq!(visits.first_visit(1), Ok(None));
+    assert_eq!(visits.first_visit(-4), Ok(None));
+
+    // The same walk, shifted by the starting frequency.
+    let visits = FirstVisits::new(&[3, 3, 4, -2, -4], -100).unwrap();
+    assert_eq!(visits.first_visit(-100), Ok(Some(Visit{j: -1, k: 0})));
+    assert_eq!(visits.first_visit(-93), Ok(Some(Visit{j: 0, k: 1})));
+    assert_eq!(visits.first_visit(-99), Ok(None));
+    assert_eq!(visits.first_visit(0), Ok(Some(Visit{j: 3, k: 23})));
+
+    // A negative total walks downwards.
+    let visits = FirstVisits::new(&[-3, -3, -4, 2, 4], 5).unwrap();
+    assert_eq!(visits.first_visit(-9), Ok(Some(Visit{j: 2, k: 1})));
+    assert_eq!(visits.first_visit(6), Ok(None));
+
+    // A zero total only ever visits the first pass.
+    let visits = FirstVisits::new(&[1, 2, -2, -1], 0).unwrap();
+    assert_eq!(visits.first_visit(1), Ok(Some(Visit{j: 0, k: 0})));
+    assert_eq!(visits.first_visit(3), Ok(Some(Visit{j: 1, k: 0})));
+    assert_eq!(visits.first_visit(4), Ok(None));
+}
+
+#[test]
+fn test_first_visit_overflow() {
+    let visits = FirstVisits::new(&[1_i64, 1], 0).unwrap();
+    assert_eq!(visits.first_visit(i64::MAX), Ok(Some(Visit{j: 0, k: i64::MAX / 2})));
+    let visits = FirstVisits::new(&[1_i64, 1], -2).unwrap();
+    assert_eq!(visits.first_visit(i64::MAX), Err(Overflow));
+}
@@ -0,0 +1,681 @@
+extern crate common;
+
+use common::Budget;
+use common::Json;
+use common::ParseError;
+use common::Solver;
+use common::SolverExtras;
+use common::ToJson;
+use common::Variant;
+use common::input;
+use common::parse;
+use common::parse::ParseErrorKind;
+use std::collections::HashMap;
+use std::convert::TryFrom;
+use std::fmt;
+use std::fmt::Debug;
+use std::fmt::Display;
+use std::hash::Hash;
+use std::io::BufRead;
+use std::hint::black_box;
+use std::num::ParseIntError;
+use std::ops::Range;
+use std::str::FromStr;
+use std::thread;
+use std::time::Instant;
+
+mod explain;
+mod first_visits;
+mod parallel;
+mod repeats;
+mod stats;
+mod streaming;
+
+pub use explain::explain_part2;
+pub use first_visits::FirstVisits;
+pub use first_visits::Visit;
+pub use parallel::solve_part2_fast_parallel;
+pub use repeats::Repeat;
+pub use repeats::Repeats;
+pub use repeats::repeats;
+pub use stats::Stats;
+pub use stats::stats;
+pub use streaming::NumberReader;
+pub use streaming::solve_part1_streaming;
+pub use streaming::solve_part2_streaming;
+pub use streaming::solve_streaming;
+
+// A signed integer type to add up frequencies in. The solvers only use checked arithmetic, so
+// that an input that drifts out of range is reported as an Overflow instead of wrapping (in
+// release builds) or panicking (in debug builds).
+pub trait Frequency: Copy + Ord + Hash + Debug + Display + FromStr<Err = ParseIntError>
+        + ToJson + Send + Sync {
+    const ZERO: Self;
+    const ONE: Self;
+
+    fn checked_add(self, other: Self) -> Option<Self>;
+    fn checked_sub(self, other: Self) -> Option<Self>;
+    fn checked_mul(self, other: Self) -> Option<Self>;
+    fn checked_div_euclid(self, other: Self) -> Option<Self>;
+    fn checked_rem_euclid(self, other: Self) -> Option<Self>;
+    // For counts, e.g. of indices.
+    fn checked_from_usize(n: usize) -> Option<Self>;
+    fn checked_to_usize(self) -> Option<usize>;
+}
+
+macro_rules! impl_frequency {
+    ($($t:ty),*) => {
+        $(
+            impl Frequency for $t {
+                const ZERO: $t = 0;
+                const ONE: $t = 1;
+
+                fn checked_add(self, other: $t) -> Option<$t> { <$t>::checked_add(self, other) }
+                fn checked_sub(self, other: $t) -> Option<$t> { <$t>::checked_sub(self, other) }
+                fn checked_mul(self, other: $t) -> Option<$t> { <$t>::checked_mul(self, other) }
+                fn checked_div_euclid(self, other: $t) -> Option<$t> {
+                    <$t>::checked_div_euclid(self, other)
+                }
+                fn checked_rem_euclid(self, other: $t) -> Option<$t> {
+                    <$t>::checked_rem_euclid(self, other)
+                }
+                fn checked_from_usize(n: usize) -> Option<$t> { <$t>::try_from(n).ok() }
+                fn checked_to_usize(self) -> Option<usize> { usize::try_from(self).ok() }
+            }
+        )*
+    }
+}
+
+impl_frequency!(i32, i64, i128);
+
+// A frequency went outside the range of the integer type it is computed in.
+#[derive(Debug, PartialEq)]
+pub struct Overflow;
+
+impl fmt::Display for Overflow {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "frequency overflow")
+    }
+}
+
+fn add<T: Frequency>(a: T, b: T) -> Result<T, Overflow> {
+    a.checked_add(b).ok_or(Overflow)
+}
+
+fn sub<T: Frequency>(a: T, b: T) -> Result<T, Overflow> {
+    a.checked_sub(b).ok_or(Overflow)
+}
+
+fn mul<T: Frequency>(a: T, b: T) -> Result<T, Overflow> {
+    a.checked_mul(b).ok_or(Overflow)
+}
+
+pub fn solve_part1<T: Frequency>(numbers: &[T]) -> Result<T, Overflow> {
+    let mut sum = T::ZERO;
+    for &number in numbers {
+        sum = add(sum, number)?;
+    }
+    Ok(sum)
+}
+
+#[test]
+fn test_part1() {
+    assert_eq!(solve_part1(&[1, 1, 1]), Ok(3));
+    assert_eq!(solve_part1(&[1, 1, -2]), Ok(0));
+    assert_eq!(solve_part1(&[-1, -2, -3]), Ok(-6));
+    assert_eq!(solve_part1(&[i32::MAX, 1]), Err(Overflow));
+    assert_eq!(solve_part1(&[i64::from(i32::MAX), 1]), Ok(i64::from(i32::MAX) + 1));
+    assert_eq!(solve_part1(&[i128::MIN, -1]), Err(Overflow));
+}
+
+// Represents a solution. Interpretation:
+//      value == sum(numbers[:i-1])
+//            == sum(numbers[:j-1]) + k * sum(numbers).
+#[derive(Clone)]
+#[derive(Debug)]
+#[derive(PartialEq)]
+pub struct Solution<T> {
+    pub value: T,
+    pub i: i32,
+    pub j: i32,
+    pub k: T,
+}
+
+impl<T: Frequency> ToJson for Solution<T> {
+    fn to_json(&self) -> Json {
+        Json::object(vec![
+            ("value", self.value.to_json()),
+            ("i", self.i.to_json()),
+            ("j", self.j.to_json()),
+            ("k", self.k.to_json())])
+    }
+}
+
+#[test]
+fn test_solution_to_json() {
+    assert_eq!(Solution{value: 10, i: 2, j: 1, k: 1}.to_json().to_string(),
+               "{\"value\":10,\"i\":2,\"j\":1,\"k\":1}");
+}
+
+impl<T: Frequency> Solution<T> {
+    // Returns true iff self is an earlier (and therefore better) solution than other.
+    fn better_than(&self, other: &Solution<T>) -> bool {
+        (self.k, self.j, self.i) < (other.k, other.j, other.i)
+    }
+}
+
+// Why solve_part2_naive_with_budget() gave up.
+#[derive(Debug, PartialEq)]
+pub enum NaiveError {
+    Overflow,
+    // The budget ran out after this many passes, before an answer was found.
+    BudgetExceeded { passes: u64 },
+}
+
+impl From<Overflow> for NaiveError {
+    fn from(_: Overflow) -> NaiveError {
+        NaiveError::Overflow
+    }
+}
+
+impl fmt::Display for NaiveError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            NaiveError::Overflow => write!(f, "{}", Overflow),
+            NaiveError::BudgetExceeded{passes} => {
+                write!(f, "budget exceeded after {} passes", passes)
+            },
+        }
+    }
+}
+
+// Returns the solution, or None if there is no solution.
+pub fn solve_part2_naive<T: Frequency>(numbers: &[T]) -> Result<Option<Solution<T>>, Overflow> {
+    match solve_part2_naive_with_budget(numbers, &Budget::unlimited()) {
+        Ok(soln) => Ok(soln),
+        Err(NaiveError::Overflow) => Err(Overflow),
+        Err(NaiveError::BudgetExceeded{..}) => unreachable!("an unlimited budget ran out"),
+    }
+}
+
+// As above, but gives up with BudgetExceeded once `budget` runs out. With a tiny total, the
+// answer can take any number of passes, e.g. [50, 50, -99] takes 50, and scaled up by a million
+// it takes 50 million.
+pub fn solve_part2_naive_with_budget<T: Frequency>(numbers: &[T], budget: &Budget)
+        -> Result<Option<Solution<T>>, NaiveError> {
+    let start = Instant::now();
+    if budget.exceeded(0, start) {
+        return Err(NaiveError::BudgetExceeded{passes: 0});
+    }
+
+    // First pass. If a solution is found, return it immediately.
+    let mut sum_to_i: HashMap<T, i32> = HashMap::new();
+    sum_to_i.insert(T::ZERO, -1);
+    let mut sum = T::ZERO;
+    for (j, &number) in numbers.iter().enumerate() {
+        sum = add(sum, number)?;
+        if let Some(i) = sum_to_i.get(&sum) {
+            return Ok(Some(Solution{value: sum, i: *i, j: j as i32, k: T::ZERO}));
+        }
+        sum_to_i.insert(sum, j as i32);
+    }
+
+    // Second pass. Look for solutions that involve going through the input sequence multiple
+    // times. If there is a solution, then it must involve one of the values seen in the first
+    // pass. Consider: sum(numbers[:i-1]) + ki * sum(numbers) == sum(numbers[:j-1]) + kj *
+    // sum(numbers). Then we can find a solution with ki = 0 by subtracting a multiple of
+    // sum(numbers) from both sides. Therefore we do not need to add more entries to sum_to_i for k
+    // > 0. When all the values we see have gone outside the range for k = 0, then no solution is
+    // possible.
+    let range_min = sum_to_i.keys().min().unwrap();
+    let range_max = sum_to_i.keys().max().unwrap();
+    let mut k = T::ONE;
+    let mut passes: u64 = 1;
+    loop {
+        if budget.exceeded(passes, start) {
+            return Err(NaiveError::BudgetExceeded{passes});
+        }
+        let mut found_value_in_range = false;
+        for (j, &number) in numbers.iter().enumerate() {
+            sum = add(sum, number)?;
+            if let Some(i) = sum_to_i.get(&sum) {
+                return Ok(Some(Solution{value: sum, i: *i, j: j as i32, k}));
+            }
+            if sum >= *range_min && sum <= *range_max {
+                found_value_in_range = true;
+            }
+        }
+        if !found_value_in_range {
+            return Ok(None)
+        }
+        k = add(k, T::ONE)?;
+        passes += 1;
+    }
+}
+
+#[test]
+fn test_part2_naive_budget() {
+    assert_eq!(solve_part2_naive_with_budget(&[50, 50, -99], &Budget::passes(50)),
+               Ok(Some(Solution{value: 50, i: 0, j: 2, k: 49})));
+    assert_eq!(solve_part2_naive_with_budget(&[50, 50, -99], &Budget::passes(49)),
+               Err(NaiveError::BudgetExceeded{passes: 49}));
+    assert_eq!(solve_part2_naive_with_budget(&[2, 2, 2, 7, -3], &Budget::passes(3)), Ok(None));
+    assert_eq!(solve_part2_naive_with_budget(&[1, -1],
+                                             &Budget::time(std::time::Duration::ZERO)),
+               Err(NaiveError::BudgetExceeded{passes: 0}));
+    // Scaled up, it would need 50 million passes.
+    assert_eq!(solve_part2_naive_with_budget(&[50_000_000, 50_000_000, -99_999_999],
+                                             &Budget::passes(1000)),
+               Err(NaiveError::BudgetExceeded{passes: 1000}));
+    assert_eq!(solve_part2_naive_with_budget(&[i32::MAX, 1], &Budget::passes(1)),
+               Err(NaiveError::Overflow));
+}
+
+// Info about an index in the input numbers array. Interpretation:
+// numbers[index] = multiple * sum(numbers) + modulo.
+#[derive(Copy)]
+#[derive(Debug)]
+struct IndexInfo<T> {
+    index: i32,
+    multiple: T,
+    modulo: T,
+}
+
+impl<T: Copy> Clone for IndexInfo<T> {
+    fn clone(&self) -> IndexInfo<T> { *self }
+}
+
+// Splits sum into multiple * total + modulo, where 0 <= modulo < |total|. This is Euclidean
+// division, which is exact for every sum and total; the only quotient that does not fit is
+// T::MIN / -1.
+//
+// Major gotcha: In Python % is modulus, but in C and Rust, % is remainder... Also in Python 2.7 /
+// is floor division, but in C and Rust / is division with truncation towards zero... So these work
+// completely differently for negative numbers. Euclidean division differs from Python's too when
+// total < 0, but any split works as long as sums that differ by a multiple of total get the same
+// modulo, and multiples that differ by exactly the number of passes between them.
+fn div_mod_euclid<T: Frequency>(sum: T, total: T) -> Result<(T, T), Overflow> {
+    let multiple = sum.checked_div_euclid(total).ok_or(Overflow)?;
+    let modulo = sum.checked_rem_euclid(total).ok_or(Overflow)?;
+    Ok((multiple, modulo))
+}
+
+#[test]
+fn test_div_mod_euclid() {
+    assert_eq!(div_mod_euclid(7, 3), Ok((2, 1)));
+    assert_eq!(div_mod_euclid(-7, 3), Ok((-3, 2)));
+    assert_eq!(div_mod_euclid(7, -3), Ok((-2, 1)));
+    assert_eq!(div_mod_euclid(-7, -3), Ok((3, 2)));
+    assert_eq!(div_mod_euclid(6, -3), Ok((-2, 0)));
+    assert_eq!(div_mod_euclid(-6, -3), Ok((2, 0)));
+    assert_eq!(div_mod_euclid(0, -3), Ok((0, 0)));
+
+    assert_eq!(div_mod_euclid(i64::MIN, 1), Ok((i64::MIN, 0)));
+    assert_eq!(div_mod_euclid(i64::MAX, -1), Ok((-i64::MAX, 0)));
+    assert_eq!(div_mod_euclid(i64::MIN, -1), Err(Overflow));
+    assert_eq!(div_mod_euclid(i64::MIN, i64::MAX), Ok((-2, i64::MAX - 1)));
+    assert_eq!(div_mod_euclid(i64::MAX, i64::MIN), Ok((0, i64::MAX)));
+    assert_eq!(div_mod_euclid(i64::MIN, i64::MIN), Ok((1, 0)));
+    assert_eq!(div_mod_euclid(i64::MIN + 1, i64::MIN), Ok((1, 1)));
+    assert_eq!(div_mod_euclid(i128::MIN, 7), Ok((-24305883351495604533098186245126300819, 5)));
+}
+
+// Indices grouped by the modulo of their prefix sum.
+type ModuloGroups<T> = HashMap<T, Vec<IndexInfo<T>>>;
+
+// Returns the prefix sums of the first pass, and their indices grouped by modulo when divided by
+// total, which must not be 0. The starting frequency 0 (index -1) is not included.
+fn group_by_modulo<T: Frequency>(numbers: &[T], total: T)
+        -> Result<(Vec<T>, ModuloGroups<T>), Overflow> {
+    let mut sums: Vec<T> = Vec::new();
+    let mut sum = T::ZERO;
+    let mut mod_to_index_infos: ModuloGroups<T> = HashMap::new();
+    for (j, &number) in numbers.iter().enumerate() {
+        sum = add(sum, number)?;
+        sums.push(sum);
+        let (multiple, modulo) = div_mod_euclid(sum, total)?;
+        // println!("sum={} total={} multiple={} modulo={}", sum, total, multiple, modulo);
+        if total > T::ZERO {
+            assert!(modulo >= T::ZERO && modulo < total);
+        } else {
+            assert!(modulo >= T::ZERO && add(modulo, total)? < T::ZERO);
+        }
+        let index_infos = mod_to_index_infos.entry(modulo).or_default();
+        index_infos.push(IndexInfo{index: j as i32, multiple, modulo});
+    }
+    Ok((sums, mod_to_index_infos))
+}
+
+// As above, but using a fancy O(n log n) solution.
+pub fn solve_part2_fast<T: Frequency>(numbers: &[T]) -> Result<Option<Solution<T>>, Overflow> {
+    let total = solve_part1(numbers)?;
+    // println!("total: {}", total);
+
+    // Case 1: We have k = 0. Get the solution in O(n). This always happens when total == 0, unless
+    // there are no numbers at all, and can happen for any total.
+    if let Some(soln) = solve_first_pass(numbers)? {
+        return Ok(Some(soln));
+    }
+    if total == T::ZERO {
+        return Ok(None);
+    }
+
+    // Case 2: We have k > 0. Get the solution in O(n log n).
+    let (_sums, mod_to_index_infos) = group_by_modulo(numbers, total)?;
+    solve_multiple_passes(total, mod_to_index_infos)
+}
+
+// Case 1 of solve_part2_fast(): the first repeat within the first pass, if any.
+fn solve_first_pass<T: Frequency>(numbers: &[T]) -> Result<Option<Solution<T>>, Overflow> {
+    let mut sum_to_i: HashMap<T, i32> = HashMap::new();
+    sum_to_i.insert(T::ZERO, -1);
+    let mut sum = T::ZERO;
+    for (j, &number) in numbers.iter().enumerate() {
+        sum = add(sum, number)?;
+        if let Some(i) = sum_to_i.get(&sum) {
+            return Ok(Some(Solution{value: sum, i: *i, j: j as i32, k: T::ZERO}));
+        }
+        sum_to_i.insert(sum, j as i32);
+    }
+    Ok(None)
+}
+
+// Case 2 of solve_part2_fast(): the best solution with k > 0, given the prefix sums of the first
+// pass grouped by modulo as in group_by_modulo(). All the sums seen in the first pass must be
+// distinct, so that the multiples within a modulo group are distinct too.
+fn solve_multiple_passes<T: Frequency>(total: T, mut mod_to_index_infos: ModuloGroups<T>)
+        -> Result<Option<Solution<T>>, Overflow> {
+    // The starting frequency 0 counts as index -1, with multiple 0 and modulo 0.
+    mod_to_index_infos.entry(T::ZERO).or_default()
+        .push(IndexInfo{index: -1, multiple: T::ZERO, modulo: T::ZERO});
+    // If i and j form a solution, they must be in the same modulo group.
+    let mut best_soln: Option<Solution<T>> = None;
+    for (_modulo, index_infos) in mod_to_index_infos.iter_mut() {
+        // println!("modulo={} index_infos={:?}", _modulo, index_infos);
+        best_soln = better_of(best_soln, solve_modulo_group(total, index_infos)?);
+    }
+    Ok(best_soln)
+}
+
+// The best solution within one modulo group. Sorts the group by multiple.
+fn solve_modulo_group<T: Frequency>(total: T, index_infos: &mut [IndexInfo<T>])
+        -> Result<Option<Solution<T>>, Overflow> {
+    index_infos.sort_unstable_by_key(|info| info.multiple);
+    let mut best_soln: Option<Solution<T>> = None;
+    for t in 1..index_infos.len() {
+        // println!("t={}", t);
+        let i = index_infos[t];
+        let j = index_infos[t - 1];
+        assert!(i.multiple > j.multiple);
+        // Starting from index -1 after k passes is the same place as the end of pass k - 1,
+        // which the last index already covers; and with k = 1 it is no repeat at all.
+        if j.index == -1 {
+            continue;
+        }
+        let k = sub(i.multiple, j.multiple)?;
+        let value = add(mul(i.multiple, total)?, i.modulo)?;
+        let soln = Solution{value, i: i.index, j: j.index, k};
+        let soln_is_better = match best_soln {
+            None => true,
+            Some(ref other_soln) => soln.better_than(other_soln)
+        };
+        // println!("candidate: {:?} better={}", soln, soln_is_better);
+        if soln_is_better {
+            best_soln = Some(soln);
+        }
+    }
+    Ok(best_soln)
+}
+
+// The better of two candidate solutions.
+fn better_of<T: Frequency>(a: Option<Solution<T>>, b: Option<Solution<T>>)
+        -> Option<Solution<T>> {
+    match (a, b) {
+        (Some(a), Some(b)) => Some(if b.better_than(&a) { b } else { a }),
+        (a, b) => a.or(b),
+    }
+}
+
+#[cfg(test)]
+fn check_part2(numbers: &[i32], solution: &Option<Solution<i32>>) {
+    assert_eq!(solve_part2_naive(numbers), Ok(solution.clone()));
+    assert_eq!(solve_part2_fast(numbers), Ok(solution.clone()));
+
+    // Same again, scaled up to beyond the range of i32 and i64.
+    for &scale in &[1_i128 << 40, 1_i128 << 80] {
+        let scaled = |x: i32| i128::from(x) * scale;
+        let numbers: Vec<i128> = numbers.iter().map(|&x| scaled(x)).collect();
+        let solution = solution.as_ref().map(|soln| {
+            Solution{value: scaled(soln.value), i: soln.i, j: soln.j, k: i128::from(soln.k)}
+        });
+        assert_eq!(solve_part2_naive(&numbers), Ok(solution.clone()));
+        assert_eq!(solve_part2_fast(&numbers), Ok(solution));
+    }
+}
+
+#[test]
+fn test_part2() {
+    check_part2(&[1, -1], &Some(Solution{value: 0, i: -1, j: 1, k: 0}));
+    check_part2(&[3, 3, 4, -2, -4], &Some(Solution{value: 10, i: 2, j: 1, k: 1}));
+    check_part2(&[-3, -3, -4, 2, 4], &Some(Solution{value: -10, i: 2, j: 1, k: 1}));
+    check_part2(&[-6, 3, 8, 5, -6], &Some(Solution{value: 5, i: 2, j: 1, k: 2}));
+    check_part2(&[7, 7, -2, -7, -4], &Some(Solution{value: 14, i: 1, j: 2, k: 2}));
+    check_part2(&[50, 50, -99], &Some(Solution{value: 50, i: 0, j: 2, k: 49}));
+    check_part2(&[2, 2, 2, 7, -3], &None);
+    check_part2(&[0, 1], &Some(Solution{value: 0, i: -1, j: 0, k: 0}));
+    check_part2(&[], &None);
+    check_part2(&[1, 1, -1, 5], &Some(Solution{value: 1, i: 0, j: 2, k: 0}));
+    check_part2(&[-2, 3], &Some(Solution{value: 0, i: -1, j: 0, k: 2}));
+}
+
+#[test]
+fn test_part2_overflow() {
+    assert_eq!(solve_part2_naive(&[i32::MAX, 1, -1]), Err(Overflow));
+    assert_eq!(solve_part2_fast(&[i32::MAX, 1, -1]), Err(Overflow));
+    // Fine in i64.
+    let numbers = [i64::from(i32::MAX), 1, -1];
+    let solution = Some(Solution{value: i64::from(i32::MAX), i: 0, j: 2, k: 0});
+    assert_eq!(solve_part2_naive(&numbers), Ok(solution.clone()));
+    assert_eq!(solve_part2_fast(&numbers), Ok(solution));
+}
+
+// The vector files are shared with test_solve.py, so that both implementations are held to the
+// same answers and the same tie-breaking.
+#[cfg(test)]
+fn solution_from_json(json: &Json) -> Option<Solution<i32>> {
+    match *json {
+        Json::Null => None,
+        _ => {
+            let field = |name| json.get(name).and_then(|value| value.as_int()).unwrap();
+            Some(Solution{value: field("value"), i: field("i"), j: field("j"), k: field("k")})
+        },
+    }
+}
+
+#[test]
+fn test_part2_vectors() {
+    for line in include_str!("../../vectors/part2.jsonl").lines() {
+        let vector = Json::parse(line).unwrap();
+        let numbers: Vec<i32> = vector.get("numbers").and_then(|numbers| numbers.as_array())
+            .unwrap().iter().map(|number| number.as_int().unwrap()).collect();
+        check_part2(&numbers, &solution_from_json(vector.get("expected").unwrap()));
+    }
+}
+
+#[test]
+fn test_better_than_vectors() {
+    for line in include_str!("../../vectors/better_than.jsonl").lines() {
+        let vector = Json::parse(line).unwrap();
+        let a = solution_from_json(vector.get("a").unwrap()).unwrap();
+        let b = solution_from_json(vector.get("b").unwrap()).unwrap();
+        assert_eq!(a.better_than(&b), vector.get("a_better").unwrap().as_bool().unwrap(),
+                   "{}", line);
+    }
+}
+
+// Returns the spans of the whitespace-separated words of `line_text[span]`.
+fn word_spans(line_text: &str, span: Range<usize>) -> Vec<Range<usize>> {
+    let mut spans: Vec<Range<usize>> = Vec::new();
+    let mut start = None;
+    for (i, c) in line_text[span.clone()].char_indices() {
+        if !c.is_whitespace() {
+            start = start.or(Some(span.start + i));
+        } else if let Some(word_start) = start.take() {
+            spans.push(word_start..span.start + i);
+        }
+    }
+    spans.extend(start.map(|word_start| word_start..span.end));
+    spans
+}
+
+// Parses the signed integers on one line, separated by commas and/or whitespace. Returns a result
+// per token, so that every bad token is reported.
+fn parse_line<T: Frequency>(line_text: &str) -> Vec<Result<T, ParseError>> {
+    let mut results: Vec<Result<T, ParseError>> = Vec::new();
+    let has_commas = line_text.contains(',');
+    let mut field_start = 0;
+    for field in line_text.split(',') {
+        let field_span = field_start..field_start + field.len();
+        field_start = field_span.end + 1;
+        let word_spans = word_spans(line_text, field_span.clone());
+        if word_spans.is_empty() && has_commas {
+            let span = parse::trimmed_span(field);
+            let span = field_span.start + span.start..field_span.start + span.end;
+            results.push(Err(ParseError::new(ParseErrorKind::EmptyToken, line_text, span)));
+        }
+        for span in word_spans {
+            let token = &line_text[span.clone()];
+            let digits = token.strip_prefix(['+', '-']).unwrap_or(token);
+            if digits.is_empty() || digits.contains(['+', '-']) {
+                results.push(Err(ParseError::new(ParseErrorKind::AmbiguousSign, line_text, span)));
+            } else {
+                results.push(parse::parse_int(line_text, span));
+            }
+        }
+    }
+    results
+}
+
+// Parses signed integers separated by newlines, commas and/or whitespace, as in the puzzle's
+// "+1, -2, +3, +1". The "+" is optional. An empty token (as in "1,,2" or a trailing comma) is an
+// EmptyToken error, and a sign that could belong to either of two numbers (as in "1-2" or "+ 1")
+// is an AmbiguousSign error. Numbers that do not fit in T are reported as IntegerOverflow errors.
+pub fn parse_input<T: Frequency>(input: &str) -> Result<Vec<T>, Vec<ParseError>> {
+    let mut values: Vec<T> = Vec::new();
+    let mut errors: Vec<ParseError> = Vec::new();
+    for line in input::lines(input).skip_blank() {
+        for result in parse_line(line.text) {
+            match result {
+                Ok(value) => values.push(value),
+                Err(error) => errors.push(error.at_line(line.number)),
+            }
+        }
+    }
+    if errors.is_empty() {
+        Ok(values)
+    } else {
+        Err(errors)
+    }
+}
+
+// Solves in i64, which is plenty for any real input, and reports overflow as an error.
+pub struct Day01;
+
+impl Solver for Day01 {
+    type Input = Vec<i64>;
+    type Output1 = i64;
+    type Output2 = Option<Solution<i64>>;
+
+    fn parse(input: &str) -> Result<Vec<i64>, Vec<ParseError>> {
+        parse_input(input)
+    }
+
+    fn part1(input: &Vec<i64>) -> Result<i64, String> {
+        solve_part1(input).map_err(|e| e.to_string())
+    }
+
+    fn part2(input: &Vec<i64>) -> Result<Option<Solution<i64>>, String> {
+        solve_part2_fast(input).map_err(|e| e.to_string())
+    }
+
+    fn variants() -> Vec<Variant<Vec<i64>>> {
+        vec![
+            Variant{name: "solve_part1", part: 1,
+                    run: |input| { let _ = black_box(solve_part1(input)); }},
+            Variant{name: "solve_part2_naive", part: 2,
+                    run: |input| { let _ = black_box(solve_part2_naive(input)); }},
+            Variant{name: "solve_part2_fast", part: 2,
+                    run: |input| { let _ = black_box(solve_part2_fast(input)); }},
+            Variant{name: "solve_part2_fast_parallel", part: 2,
+                    run: |input| {
+                        let num_threads = thread::available_parallelism().map_or(1, |n| n.get());
+                        let _ = black_box(solve_part2_fast_parallel(input, num_threads));
+                    }}]
+    }
+}
+
+impl SolverExtras for Day01 {
+    fn explain(input: &Vec<i64>) -> Result<String, String> {
+        explain_part2(input).map_err(|e| e.to_string())
+    }
+
+    fn stats(input: &Vec<i64>) -> Result<Json, String> {
+        stats(input).map(|stats| stats.to_json()).map_err(|e| e.to_string())
+    }
+
+    fn part2_reference(input: &Vec<i64>, budget: &Budget)
+            -> Result<Option<Option<Solution<i64>>>, String> {
+        match solve_part2_naive_with_budget(input, budget) {
+            Ok(soln) => Ok(Some(soln)),
+            Err(NaiveError::BudgetExceeded{..}) => Ok(None),
+            Err(e) => Err(e.to_string()),
+        }
+    }
+
+    // Stops at the first bad token, since the input may be too big to look for them all.
+    fn solve_stream(reader: &mut dyn BufRead) -> Result<(i64, Option<Solution<i64>>), String> {
+        let mut error = None;
+        let numbers = NumberReader::new(reader)
+            .map_while(|number| number.map_err(|e| error = Some(e)).ok());
+        let answers = solve_streaming(numbers);
+        if let Some(message) = error {
+            return Err(message);
+        }
+        answers.map_err(|e| e.to_string())
+    }
+}
+
+#[test]
+fn test_parse_input() {
+    assert_eq!(parse_input("+1\n-2\n+3\n"), Ok(vec![1, -2, 3]));
+    assert_eq!(parse_input("\u{feff}+1\r\n-2\r\n\r\n+3\r\n"), Ok(vec![1, -2, 3]));
+    let errors = parse_input::<i32>("+1\n+x\n  3000000000\n").unwrap_err();
+    assert_eq!(errors.iter().map(|e| (e.line, e.span.clone())).collect::<Vec<_>>(),
+               vec![(2, 0..2), (3, 2..12)]);
+    assert_eq!(parse_input("  3000000000\n"), Ok(vec![3_000_000_000_i64]));
+}
+
+#[test]
+fn test_parse_input_inline() {
+    assert_eq!(parse_input("+1, -2, +3, +1"), Ok(vec![1, -2, 3, 1]));
+    assert_eq!(parse_input("1,-2,3\n+4 5\t-6\n7 , 8"), Ok(vec![1, -2, 3, 4, 5, -6, 7, 8]));
+
+    let kinds = |input: &str| {
+        parse_input::<i32>(input).unwrap_err().into_iter()
+            .map(|e| (e.line, e.span, e.kind))
+            .collect::<Vec<_>>()
+    };
+    assert_eq!(kinds("1,,2\n3, \n,4"),
+               vec![(1, 2..2, ParseErrorKind::EmptyToken), (2, 2..2, ParseErrorKind::EmptyToken),
+                    (3, 0..0, ParseErrorKind::EmptyToken)]);
+    assert_eq!(kinds("1-2, + 3, +-4, 5+"),
+               vec![(1, 0..3, ParseErrorKind::AmbiguousSign),
+                    (1, 5..6, ParseErrorKind::AmbiguousSign),
+                    (1, 10..13, ParseErrorKind::AmbiguousSign),
+                    (1, 15..17, ParseErrorKind::AmbiguousSign)]);
+    assert_eq!(kinds("+1, x, 3000000000"),
+               vec![(1, 4..5, ParseErrorKind::UnknownFormat),
+                    (1, 7..17, ParseErrorKind::IntegerOverflow)]);
+    assert_eq!(parse_input::<i32>("1,,2").unwrap_err()[0].to_string(),
+               "line 1, bytes 2..2: empty token: \"\"");
+}
@@ -0,0 +1,199 @@
+// solve_part2_fast(), with the prefix sums computed and bucketed by modulo in contiguous chunks,
+// and the modulo groups solved, on several threads. A first pass on a thread of its own races
+// them, so that an early repeat is still found early.
+
+use std::collections::HashMap;
+use std::sync::atomic::AtomicBool;
+use std::sync::atomic::Ordering;
+use std::thread;
+
+use super::Frequency;
+use super::IndexInfo;
+use super::ModuloGroups;
+use super::Overflow;
+use super::Solution;
+use super::add;
+use super::better_of;
+use super::div_mod_euclid;
+use super::mul;
+use super::solve_modulo_group;
+use super::solve_part1;
+use super::solve_part2_fast;
+
+// How many numbers the workers go through between checks of whether to stop.
+const STOP_CHECK_INTERVAL: usize = 1 << 12;
+
+// Returns the same as solve_part2_fast(), using up to `num_threads` threads besides the one that
+// looks for a repeat within the first pass.
+pub fn solve_part2_fast_parallel<T: Frequency>(numbers: &[T], num_threads: usize)
+        -> Result<Option<Solution<T>>, Overflow> {
+    // On one thread, the race below only slows down both sides.
+    if num_threads <= 1 {
+        return solve_part2_fast(numbers);
+    }
+    let stop = AtomicBool::new(false);
+    let (first_pass_soln, chunked_soln) = thread::scope(|scope| {
+        let first_pass = scope.spawn(|| {
+            let soln = solve_first_pass_until(numbers, &stop);
+            if let Ok(Some(_)) = soln {
+                stop.store(true, Ordering::Relaxed);
+            }
+            soln
+        });
+        let chunked_soln = solve_chunked(numbers, num_threads, &stop);
+        if chunked_soln.is_some() {
+            stop.store(true, Ordering::Relaxed);
+        }
+        (first_pass.join().unwrap(), chunked_soln)
+    });
+    match (chunked_soln, first_pass_soln) {
+        (Some(soln), _) => Ok(soln),
+        (None, Ok(Some(soln))) => Ok(Some(soln)),
+        (None, _) => solve_part2_fast(numbers),
+    }
+}
+
+// solve_first_pass(), but gives up with Ok(None) once `stop` is set.
+fn solve_first_pass_until<T: Frequency>(numbers: &[T], stop: &AtomicBool)
+        -> Result<Option<Solution<T>>, Overflow> {
+    let mut sum_to_i: HashMap<T, i32> = HashMap::new();
+    sum_to_i.insert(T::ZERO, -1);
+    let mut sum = T::ZERO;
+    for (j, &number) in numbers.iter().enumerate() {
+        if j % STOP_CHECK_INTERVAL == 0 && stop.load(Ordering::Relaxed) {
+            return Ok(None);
+        }
+        sum = add(sum, number)?;
+        if let Some(i) = sum_to_i.get(&sum) {
+            return Ok(Some(Solution{value: sum, i: *i, j: j as i32, k: T::ZERO}));
+        }
+        sum_to_i.insert(sum, j as i32);
+    }
+    Ok(None)
+}
+
+// The full answer, or None if it overflowed, the total is 0 (only a repeat within the first pass
+// is possible), or `stop` was set. The workers' answers are merged with better_than().
+fn solve_chunked<T: Frequency>(numbers: &[T], num_threads: usize, stop: &AtomicBool)
+        -> Option<Option<Solution<T>>> {
+    let num_workers = num_threads.clamp(1, numbers.len().max(1));
+    let chunks: Vec<&[T]> = numbers.chunks(numbers.len().div_ceil(num_workers).max(1)).collect();
+    let num_shards = chunks.len();
+
+    // 1. The total, and the sum before each chunk.
+    let mut starts: Vec<(usize, T)> = Vec::new();
+    let mut start = (0, T::ZERO);
+    for (chunk, chunk_sum) in chunks.iter().zip(in_parallel(chunks.clone(), solve_part1)) {
+        starts.push(start);
+        start = (start.0 + chunk.len(), add(start.1, chunk_sum.ok()?).ok()?);
+    }
+    let total = start.1;
+    if total == T::ZERO {
+        return None;
+    }
+
+    // 2. Each chunk's index infos, bucketed by shard.
+    let bucketed = in_parallel(chunks.into_iter().zip(starts).collect(), |(chunk, start)| {
+        bucket_chunk(chunk, start, total, num_shards, stop)
+    });
+    let mut shards: Vec<Vec<Vec<IndexInfo<T>>>> = vec![Vec::new(); num_shards];
+    for buckets in bucketed {
+        for (shard, bucket) in shards.iter_mut().zip(buckets?) {
+            shard.push(bucket);
+        }
+    }
+
+    // 3. The best solution in each shard's modulo groups.
+    let mut best_soln: Option<Solution<T>> = None;
+    for soln in in_parallel(shards, |buckets| solve_shard(buckets, total, stop)) {
+        best_soln = better_of(best_soln, soln?);
+    }
+    Some(best_soln)
+}
+
+// Splits the prefix sums of `chunk`, which starts at index start.0 after the sum start.1, by
+// shard. The starting frequency 0 (index -1) goes with the first chunk. Every bucket is in index
+// order. None if it overflowed or `stop` was set.
+fn bucket_chunk<T: Frequency>(chunk: &[T], start: (usize, T), total: T, num_shards: usize,
+                              stop: &AtomicBool) -> Option<Vec<Vec<IndexInfo<T>>>> {
+    let num_shards_t = T::checked_from_usize(num_shards)?;
+    let shard_of = |modulo: T| modulo.checked_rem_euclid(num_shards_t)?.checked_to_usize();
+    let mut buckets: Vec<Vec<IndexInfo<T>>> = vec![Vec::new(); num_shards];
+    let (first_index, mut sum) = start;
+    if first_index == 0 {
+        buckets[shard_of(T::ZERO)?].push(IndexInfo{index: -1, multiple: T::ZERO, modulo: T::ZERO});
+    }
+    for (j, &number) in chunk.iter().enumerate() {
+        if j % STOP_CHECK_INTERVAL == 0 && stop.load(Ordering::Relaxed) {
+            return None;
+        }
+        sum = add(sum, number).ok()?;
+        let (multiple, modulo) = div_mod_euclid(sum, total).ok()?;
+        let index = (first_index + j) as i32;
+        buckets[shard_of(modulo)?].push(IndexInfo{index, multiple, modulo});
+    }
+    Some(buckets)
+}
+
+// The best solution in the modulo groups of one shard, given its buckets from every chunk in
+// order. None if it overflowed or `stop` was set.
+fn solve_shard<T: Frequency>(buckets: Vec<Vec<IndexInfo<T>>>, total: T, stop: &AtomicBool)
+        -> Option<Option<Solution<T>>> {
+    let mut mod_to_index_infos: ModuloGroups<T> = HashMap::new();
+    for info in buckets.into_iter().flatten() {
+        mod_to_index_infos.entry(info.modulo).or_default().push(info);
+    }
+    let mut best_soln: Option<Solution<T>> = None;
+    for index_infos in mod_to_index_infos.values_mut() {
+        if stop.load(Ordering::Relaxed) {
+            return None;
+        }
+        // A stable sort, so that equal multiples stay in index order. Equal multiples are equal
+        // sums, i.e. a repeat within the first pass, which saves a hash map of every sum.
+        index_infos.sort_by_key(|info| info.multiple);
+        let mut first_pass_soln: Option<Solution<T>> = None;
+        for pair in index_infos.windows(2) {
+            if pair[0].multiple == pair[1].multiple {
+                let value = add(mul(pair[1].multiple, total).ok()?, pair[1].modulo).ok()?;
+                let soln = Solution{value, i: pair[0].index, j: pair[1].index, k: T::ZERO};
+                first_pass_soln = better_of(first_pass_soln, Some(soln));
+            }
+        }
+        // solve_modulo_group() needs distinct sums; if there is a repeat within the first pass,
+        // it beats any solution with k > 0 anyway.
+        let soln = match first_pass_soln {
+            Some(soln) => Some(soln),
+            None => solve_modulo_group(total, index_infos).ok()?,
+        };
+        best_soln = better_of(best_soln, soln);
+    }
+    Some(best_soln)
+}
+
+// Runs `f` on each item on a thread of its own, and returns the results in order.
+fn in_parallel<A: Send, R: Send, F: Fn(A) -> R + Sync>(items: Vec<A>, f: F) -> Vec<R> {
+    let f = &f;
+    thread::scope(|scope| {
+        let handles: Vec<_> = items.into_iter().map(|item| scope.spawn(move || f(item))).collect();
+        handles.into_iter().map(|handle| handle.join().unwrap()).collect()
+    })
+}
+
+#[test]
+fn test_part2_fast_parallel() {
+    let inputs: Vec<Vec<i64>> = vec![
+        vec![1, -1], vec![3, 3, 4, -2, -4], vec![-6, 3, 8, 5, -6], vec![7, 7, -2, -7, -4],
+        vec![50, 50, -99], vec![2, 2, 2, 7, -3], vec![-2, 3],
+        (1..2000).map(|x| x * 7919 % 2003 - 1000).collect(),
+        // Splitting i64::MIN by a total of -1 overflows, but the first pass repeats -1 before
+        // that matters.
+        vec![i64::MIN, i64::MAX, 0, 1, -1]];
+    for numbers in &inputs {
+        for num_threads in [0, 1, 2, 3, 16] {
+            assert_eq!(solve_part2_fast_parallel(numbers, num_threads),
+                       super::solve_part2_fast(numbers),
+                       "{:?} with {} threads", numbers, num_threads);
+        }
+    }
+    assert_eq!(solve_part2_fast_parallel(&[i32::MAX, 1], 4), Err(Overflow));
+}
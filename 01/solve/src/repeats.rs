@@ -0,0 +1,230 @@
+// Every frequency that is reached twice, not just the first one, in the order they are reached
+// the second time.
+//
+// Within a modulo group, the frequency multiple * total + modulo is reached at index j on pass
+// multiple - multiple(j), for every index j in the group with multiple(j) <= multiple. So its
+// first two visits come from the two indices with the largest multiples up to it. Those two
+// indices stay the same until the multiple passes that of another index in the group. Each such
+// stretch is a Run: the same i and j, with k going up by one per frequency. Merging the runs of
+// every group with a heap gives the repeats in order, without simulating any passes.
+
+use std::cmp::Reverse;
+use std::collections::BinaryHeap;
+use std::collections::HashMap;
+
+use super::Frequency;
+use super::IndexInfo;
+use super::Overflow;
+use super::add;
+use super::group_by_modulo;
+use super::mul;
+use super::solve_part1;
+use super::sub;
+
+// Interpretation: value is first reached at index i on pass ki, and again at index j on pass k.
+// As in Solution, index -1 is the starting frequency, before the first pass.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Repeat<T> {
+    pub value: T,
+    pub i: i32,
+    pub ki: T,
+    pub j: i32,
+    pub k: T,
+}
+
+// The repeats of the frequencies with multiples next..=last (or next.. if last is None). Each is
+// first reached at index `first`, and again at index `second`.
+struct Run<T> {
+    first: IndexInfo<T>,
+    second: IndexInfo<T>,
+    next: T,
+    last: Option<T>,
+}
+
+pub struct Repeats<T> {
+    total: T,
+    sums: Vec<T>,
+    runs: Vec<Run<T>>,
+    // (k, j, run) for the next repeat of each run that has one left.
+    heap: BinaryHeap<Reverse<(T, i32, usize)>>,
+    overflowed: bool,
+}
+
+// Returns an iterator over the repeats of `numbers`, in better_than order. The first one is the
+// answer to part 2. Preprocessing takes O(n log n), and each repeat after that O(log n), no matter
+// how many passes apart they are. The iterator is endless unless there are no repeats from some
+// pass on (e.g. when total == 0, every frequency is repeated by pass 1, and then there are no
+// new ones); it yields an Overflow once the frequencies go out of range, and then stops.
+pub fn repeats<T: Frequency>(numbers: &[T]) -> Result<Repeats<T>, Overflow> {
+    let total = solve_part1(numbers)?;
+    let start = IndexInfo{index: -1, multiple: T::ZERO, modulo: T::ZERO};
+    let mut runs: Vec<Run<T>> = Vec::new();
+    let sums: Vec<T>;
+    if total == T::ZERO {
+        // Every pass visits the same frequencies. Each one is repeated within the first pass, or
+        // else at the same index on the second. The multiples are only there to make k and ki
+        // come out right.
+        let mut sum = T::ZERO;
+        let mut partial_sums: Vec<T> = Vec::new();
+        let mut sum_to_indices: HashMap<T, Vec<i32>> = HashMap::new();
+        sum_to_indices.insert(T::ZERO, vec![-1]);
+        for (j, &number) in numbers.iter().enumerate() {
+            sum = add(sum, number)?;
+            partial_sums.push(sum);
+            sum_to_indices.entry(sum).or_default().push(j as i32);
+        }
+        for (sum, indices) in sum_to_indices {
+            // Only with no numbers at all: there are no passes then, so nothing is repeated.
+            if indices == [-1] {
+                continue;
+            }
+            let info = |index, multiple| IndexInfo{index, multiple, modulo: sum};
+            let (first, second, k) = if indices.len() >= 2 {
+                (info(indices[0], T::ZERO), info(indices[1], T::ZERO), T::ZERO)
+            } else {
+                (info(indices[0], T::ONE), info(indices[0], T::ZERO), T::ONE)
+            };
+            runs.push(Run{first, second, next: k, last: Some(k)});
+        }
+        sums = partial_sums;
+    } else {
+        let (partial_sums, mod_to_index_infos) = group_by_modulo(numbers, total)?;
+        for (modulo, mut index_infos) in mod_to_index_infos {
+            // Sorted so that for any multiple M, the indices with multiples up to M are a prefix,
+            // and the last two of the prefix are the first two visits to M.
+            index_infos.sort_unstable_by_key(|info| (info.multiple, Reverse(info.index)));
+            let mut group_runs: Vec<Run<T>> = Vec::new();
+            for t in 1..index_infos.len() {
+                let next = index_infos[t].multiple;
+                let last = match index_infos.get(t + 1) {
+                    Some(info) if info.multiple == next => continue,
+                    Some(info) => Some(sub(info.multiple, T::ONE)?),
+                    None => None,
+                };
+                group_runs.push(
+                    Run{first: index_infos[t], second: index_infos[t - 1], next, last});
+            }
+            if modulo == T::ZERO {
+                // The starting frequency is the first visit to 0, but to nothing else: starting
+                // again after k passes is the same place as the end of pass k - 1.
+                group_runs = split_at_zero(group_runs)?;
+                if let Some(&second) = index_infos.iter().rev()
+                        .find(|info| info.multiple <= T::ZERO) {
+                    group_runs.push(Run{first: start, second, next: T::ZERO, last: Some(T::ZERO)});
+                }
+            }
+            runs.extend(group_runs);
+        }
+        sums = partial_sums;
+    }
+
+    let mut repeats = Repeats{total, sums, runs, heap: BinaryHeap::new(), overflowed: false};
+    for r in 0..repeats.runs.len() {
+        repeats.queue(r);
+    }
+    Ok(repeats)
+}
+
+// Removes multiple 0 from the runs, since it is first reached at the start.
+fn split_at_zero<T: Frequency>(runs: Vec<Run<T>>) -> Result<Vec<Run<T>>, Overflow> {
+    let mut split_runs: Vec<Run<T>> = Vec::new();
+    for run in runs {
+        let covers_zero = run.next <= T::ZERO && run.last.is_none_or(|last| last >= T::ZERO);
+        if !covers_zero {
+            split_runs.push(run);
+            continue;
+        }
+        if run.next < T::ZERO {
+            split_runs.push(Run{last: Some(sub(T::ZERO, T::ONE)?), ..run});
+        }
+        if run.last != Some(T::ZERO) {
+            split_runs.push(Run{next: T::ONE, ..run});
+        }
+    }
+    Ok(split_runs)
+}
+
+impl<T: Frequency> Repeats<T> {
+    // Pushes the next repeat of run r onto the heap.
+    fn queue(&mut self, r: usize) {
+        let run = &self.runs[r];
+        // Passes beyond T's range cannot be counted, so the run ends there.
+        if let Ok(k) = sub(run.next, run.second.multiple) {
+            self.heap.push(Reverse((k, run.second.index, r)));
+        }
+    }
+
+    fn sum_at(&self, index: i32) -> T {
+        if index == -1 { T::ZERO } else { self.sums[index as usize] }
+    }
+}
+
+impl<T: Frequency> Iterator for Repeats<T> {
+    type Item = Result<Repeat<T>, Overflow>;
+
+    fn next(&mut self) -> Option<Result<Repeat<T>, Overflow>> {
+        if self.overflowed {
+            return None;
+        }
+        let Reverse((k, j, r)) = self.heap.pop()?;
+        let multiple = self.runs[r].next;
+        let first = self.runs[r].first;
+        let repeat = sub(multiple, first.multiple).and_then(|ki| {
+            let value = add(self.sum_at(j), mul(k, self.total)?)?;
+            Ok(Repeat{value, i: first.index, ki, j, k})
+        });
+        if repeat.is_err() {
+            self.overflowed = true;
+            return Some(repeat);
+        }
+
+        let run = &mut self.runs[r];
+        if run.last != Some(multiple) {
+            if let Ok(next) = add(multiple, T::ONE) {
+                run.next = next;
+                self.queue(r);
+            }
+        }
+        Some(repeat)
+    }
+}
+
+#[cfg(test)]
+fn first_repeats(numbers: &[i32], n: usize) -> Vec<(i32, i32, i32, i32, i32)> {
+    repeats(numbers).unwrap().take(n)
+        .map(|repeat| {
+            let repeat = repeat.unwrap();
+            (repeat.value, repeat.i, repeat.ki, repeat.j, repeat.k)
+        })
+        .collect()
+}
+
+#[test]
+fn test_repeats() {
+    // (value, i, ki, j, k)
+    assert_eq!(first_repeats(&[1, -1], 5), vec![(0, -1, 0, 1, 0), (1, 0, 0, 0, 1)]);
+    assert_eq!(first_repeats(&[3, 3, 4, -2, -4], 6),
+               vec![(10, 2, 0, 1, 1), (8, 3, 0, 4, 1), (14, 2, 1, 1, 2), (12, 3, 1, 4, 2),
+                    (18, 2, 2, 1, 3), (16, 3, 2, 4, 3)]);
+    assert_eq!(first_repeats(&[-6, 3, 8, 5, -6], 3),
+               vec![(5, 2, 0, 1, 2), (9, 2, 1, 1, 3), (10, 3, 0, 0, 4)]);
+    assert!(repeats(&[2, 2, 2, 7, -3]).unwrap().next().is_none());
+}
+
+#[test]
+fn test_repeats_far_apart() {
+    // The first repeat is a trillion passes in, and then there is one per pass.
+    let numbers = [1_000_000_000_000_i64, -999_999_999_999];
+    let mut found = repeats(&numbers).unwrap();
+    assert_eq!(found.next(), Some(Ok(Repeat{value: 1_000_000_000_000, i: 0, ki: 0, j: 1,
+                                            k: 999_999_999_999})));
+    assert_eq!(found.nth(998), Some(Ok(Repeat{value: 1_000_000_000_999, i: 0, ki: 999, j: 1,
+                                              k: 1_000_000_000_998})));
+}
+
+#[test]
+fn test_repeats_overflow() {
+    let mut found = repeats(&[i32::MAX - 10, -(i32::MAX - 10) + 1]).unwrap();
+    assert_eq!(found.by_ref().take_while(|repeat| repeat.is_ok()).count(), 11);
+    assert_eq!(found.next(), None);
+}
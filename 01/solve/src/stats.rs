@@ -0,0 +1,119 @@
+// Numbers that describe the shape of a frequency walk, to help understand why some inputs take
+// solve_part2_naive hundreds of passes.
+
+use std::collections::HashMap;
+
+use common::Json;
+use common::ToJson;
+
+use super::Frequency;
+use super::Overflow;
+use super::add;
+use super::div_mod_euclid;
+use super::group_by_modulo;
+use super::mul;
+use super::solve_part1;
+use super::solve_part2_fast;
+use super::sub;
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct Stats<T> {
+    // The drift per pass, i.e. the answer to part 1.
+    pub total: T,
+    // The lowest and highest frequencies on the first pass, counting the start at 0.
+    pub min_sum: T,
+    pub max_sum: T,
+    // The most prefix sums (counting the start) in one modulo group mod total, or with one value
+    // when total == 0. Only sums in the same group can ever repeat each other.
+    pub largest_modulo_group: usize,
+    // Any repeat has to land within [min_sum, max_sum], which every pass after this one misses
+    // entirely. This is the range argument that stops solve_part2_naive.
+    pub last_possible_pass: T,
+    pub repeat_possible: bool,
+    // How many frequencies are reached, counting the start, before the first repeat. None if
+    // there is no repeat, i.e. every frequency is new forever.
+    pub distinct_before_repeat: Option<T>,
+}
+
+impl<T: Frequency> ToJson for Stats<T> {
+    fn to_json(&self) -> Json {
+        Json::object(vec![
+            ("total", self.total.to_json()),
+            ("min_sum", self.min_sum.to_json()),
+            ("max_sum", self.max_sum.to_json()),
+            ("largest_modulo_group", self.largest_modulo_group.to_json()),
+            ("last_possible_pass", self.last_possible_pass.to_json()),
+            ("repeat_possible", self.repeat_possible.to_json()),
+            ("distinct_before_repeat", self.distinct_before_repeat.to_json())])
+    }
+}
+
+pub fn stats<T: Frequency>(numbers: &[T]) -> Result<Stats<T>, Overflow> {
+    let total = solve_part1(numbers)?;
+    let mut min_sum = T::ZERO;
+    let mut max_sum = T::ZERO;
+    let mut sum = T::ZERO;
+    for &number in numbers {
+        sum = add(sum, number)?;
+        min_sum = min_sum.min(sum);
+        max_sum = max_sum.max(sum);
+    }
+
+    let largest_modulo_group;
+    let last_possible_pass;
+    if total == T::ZERO {
+        let mut sum_to_count: HashMap<T, usize> = HashMap::new();
+        sum_to_count.insert(T::ZERO, 1);
+        let mut sum = T::ZERO;
+        for &number in numbers {
+            sum = add(sum, number)?;
+            *sum_to_count.entry(sum).or_default() += 1;
+        }
+        largest_modulo_group = sum_to_count.values().cloned().max().unwrap_or(0);
+        // The last prefix sum is 0 again, so the first pass always repeats the start.
+        last_possible_pass = T::ZERO;
+    } else {
+        let (_sums, mod_to_index_infos) = group_by_modulo(numbers, total)?;
+        largest_modulo_group = mod_to_index_infos.iter()
+            .map(|(&modulo, index_infos)| {
+                if modulo == T::ZERO { index_infos.len() + 1 } else { index_infos.len() }
+            })
+            .max()
+            .unwrap_or(1);
+        let drift = if total > T::ZERO { total } else { sub(T::ZERO, total)? };
+        last_possible_pass = div_mod_euclid(sub(max_sum, min_sum)?, drift)?.0;
+    }
+
+    let distinct_before_repeat = match solve_part2_fast(numbers)? {
+        // Every step up to the repeat reaches a new frequency, and so does the start.
+        Some(soln) => {
+            let len = T::checked_from_usize(numbers.len()).ok_or(Overflow)?;
+            let j = T::checked_from_usize(soln.j as usize).ok_or(Overflow)?;
+            Some(add(add(mul(soln.k, len)?, j)?, T::ONE)?)
+        },
+        None => None,
+    };
+    Ok(Stats{total, min_sum, max_sum, largest_modulo_group, last_possible_pass,
+             repeat_possible: distinct_before_repeat.is_some(), distinct_before_repeat})
+}
+
+#[test]
+fn test_stats() {
+    // Frequencies: 0 | 3 6 10 8 4 | 7 10, with the repeat at index 1 on pass 1.
+    assert_eq!(stats(&[3, 3, 4, -2, -4]), Ok(Stats{
+        total: 4, min_sum: 0, max_sum: 10, largest_modulo_group: 3, last_possible_pass: 2,
+        repeat_possible: true, distinct_before_repeat: Some(7)}));
+    assert_eq!(stats(&[-3, -3, -4, 2, 4]).unwrap().last_possible_pass, 2);
+    // The naive solver needs 49 passes for this one.
+    assert_eq!(stats(&[50, 50, -99]), Ok(Stats{
+        total: 1, min_sum: 0, max_sum: 100, largest_modulo_group: 4, last_possible_pass: 100,
+        repeat_possible: true, distinct_before_repeat: Some(150)}));
+    assert_eq!(stats(&[2, 2, 2, 7, -3]), Ok(Stats{
+        total: 10, min_sum: 0, max_sum: 13, largest_modulo_group: 2, last_possible_pass: 1,
+        repeat_possible: false, distinct_before_repeat: None}));
+    // With a zero total, the groups are the equal prefix sums: 0 at the start and at the end.
+    assert_eq!(stats(&[1, -1]), Ok(Stats{
+        total: 0, min_sum: 0, max_sum: 1, largest_modulo_group: 2, last_possible_pass: 0,
+        repeat_possible: true, distinct_before_repeat: Some(2)}));
+    assert_eq!(stats(&[i32::MAX, 1]), Err(Overflow));
+}
@@ -0,0 +1,184 @@
+// Solvers that read the numbers one at a time, so that a frequency log too big for memory can be
+// checked straight from a pipe.
+//
+// Part 1 only needs the running sum. Part 2 needs the first index of every prefix sum of the first
+// pass: to spot a repeat within it, and otherwise for the modulo analysis of solve_part2_fast(),
+// which cannot start until the total is known at the end of the stream. The numbers themselves are
+// never kept. Indices are i32, as in Solution, so a stream of more than i32::MAX numbers is an
+// Overflow.
+
+use std::collections::HashMap;
+use std::convert::TryFrom;
+use std::io::BufRead;
+use std::vec;
+
+use common::ParseError;
+
+use super::Frequency;
+use super::IndexInfo;
+use super::ModuloGroups;
+use super::Overflow;
+use super::Solution;
+use super::add;
+use super::div_mod_euclid;
+use super::parse_line;
+use super::solve_multiple_passes;
+
+pub fn solve_part1_streaming<T: Frequency, I: IntoIterator<Item = T>>(numbers: I)
+        -> Result<T, Overflow> {
+    let mut sum = T::ZERO;
+    for number in numbers {
+        sum = add(sum, number)?;
+    }
+    Ok(sum)
+}
+
+// The prefix sums of the first pass so far, and the first repeat among them, if any.
+struct FirstPass<T> {
+    sum: T,
+    len: usize,
+    // The first index of each prefix sum, with the start as index -1. Emptied once a repeat is
+    // found, since then it is not needed any more.
+    sum_to_i: HashMap<T, i32>,
+    repeat: Option<Solution<T>>,
+}
+
+impl<T: Frequency> FirstPass<T> {
+    fn new() -> FirstPass<T> {
+        let mut sum_to_i: HashMap<T, i32> = HashMap::new();
+        sum_to_i.insert(T::ZERO, -1);
+        FirstPass{sum: T::ZERO, len: 0, sum_to_i, repeat: None}
+    }
+
+    fn push(&mut self, number: T) -> Result<(), Overflow> {
+        self.sum = add(self.sum, number)?;
+        let j = i32::try_from(self.len).map_err(|_| Overflow)?;
+        self.len += 1;
+        if self.repeat.is_some() {
+            return Ok(());
+        }
+        match self.sum_to_i.get(&self.sum) {
+            Some(&i) => {
+                self.repeat = Some(Solution{value: self.sum, i, j, k: T::ZERO});
+                self.sum_to_i = HashMap::new();
+            },
+            None => {
+                self.sum_to_i.insert(self.sum, j);
+            },
+        }
+        Ok(())
+    }
+
+    // Solves part 2 once the whole pass has been pushed.
+    fn finish(self) -> Result<Option<Solution<T>>, Overflow> {
+        if self.repeat.is_some() {
+            return Ok(self.repeat);
+        }
+        // Without a repeat in the first pass, total != 0, since the last prefix sum would repeat
+        // the start.
+        let total = self.sum;
+        let mut mod_to_index_infos: ModuloGroups<T> = HashMap::new();
+        for (sum, index) in self.sum_to_i {
+            if index == -1 {
+                continue;
+            }
+            let (multiple, modulo) = div_mod_euclid(sum, total)?;
+            mod_to_index_infos.entry(modulo).or_default()
+                .push(IndexInfo{index, multiple, modulo});
+        }
+        solve_multiple_passes(total, mod_to_index_infos)
+    }
+}
+
+// As solve_part2_fast(), but stops reading as soon as there is a repeat within the first pass.
+pub fn solve_part2_streaming<T: Frequency, I: IntoIterator<Item = T>>(numbers: I)
+        -> Result<Option<Solution<T>>, Overflow> {
+    let mut first_pass = FirstPass::new();
+    for number in numbers {
+        first_pass.push(number)?;
+        if first_pass.repeat.is_some() {
+            break;
+        }
+    }
+    first_pass.finish()
+}
+
+// Solves both parts in one read of the stream, since a pipe can only be read once.
+pub fn solve_streaming<T: Frequency, I: IntoIterator<Item = T>>(numbers: I)
+        -> Result<(T, Option<Solution<T>>), Overflow> {
+    let mut first_pass = FirstPass::new();
+    for number in numbers {
+        first_pass.push(number)?;
+    }
+    Ok((first_pass.sum, first_pass.finish()?))
+}
+
+// Reads numbers in any format parse_input() accepts, one line at a time. Yields an error message
+// for each bad token and for a failed read.
+pub struct NumberReader<R, T> {
+    reader: R,
+    line: String,
+    line_number: usize,
+    pending: vec::IntoIter<Result<T, ParseError>>,
+}
+
+impl<R: BufRead, T: Frequency> NumberReader<R, T> {
+    pub fn new(reader: R) -> NumberReader<R, T> {
+        NumberReader{reader, line: String::new(), line_number: 0, pending: Vec::new().into_iter()}
+    }
+}
+
+impl<R: BufRead, T: Frequency> Iterator for NumberReader<R, T> {
+    type Item = Result<T, String>;
+
+    fn next(&mut self) -> Option<Result<T, String>> {
+        loop {
+            if let Some(result) = self.pending.next() {
+                return Some(result.map_err(|e| e.at_line(self.line_number).to_string()));
+            }
+            self.line.clear();
+            match self.reader.read_line(&mut self.line) {
+                Ok(0) => return None,
+                Ok(_) => {
+                    self.line_number += 1;
+                    let text = if self.line_number == 1 {
+                        self.line.trim_start_matches('\u{feff}')
+                    } else {
+                        &self.line
+                    };
+                    // parse_line() treats the "\r" and "\n" at the end as whitespace.
+                    if !text.trim().is_empty() {
+                        self.pending = parse_line(text).into_iter();
+                    }
+                },
+                Err(e) => return Some(Err(format!("could not read input: {}", e))),
+            }
+        }
+    }
+}
+
+#[test]
+fn test_solve_streaming() {
+    assert_eq!(solve_part1_streaming(vec![1, 1, -2]), Ok(0));
+    assert_eq!(solve_streaming(vec![3, 3, 4, -2, -4]),
+               Ok((4, Some(Solution{value: 10, i: 2, j: 1, k: 1}))));
+    assert_eq!(solve_streaming(vec![-6, 3, 8, 5, -6]),
+               Ok((4, Some(Solution{value: 5, i: 2, j: 1, k: 2}))));
+    assert_eq!(solve_streaming(vec![1, 1, -1, 5]),
+               Ok((6, Some(Solution{value: 1, i: 0, j: 2, k: 0}))));
+    assert_eq!(solve_streaming(vec![2, 2, 2, 7, -3]), Ok((10, None)));
+    assert_eq!(solve_streaming(vec![i32::MAX, 1]), Err(Overflow));
+
+    // Nothing after a repeat within the first pass is read.
+    let numbers = vec![1, -1].into_iter().chain((0..).map(|_| -> i32 { panic!("read too far") }));
+    assert_eq!(solve_part2_streaming(numbers), Ok(Some(Solution{value: 0, i: -1, j: 1, k: 0})));
+}
+
+#[test]
+fn test_number_reader() {
+    let read = |text: &str| NumberReader::new(text.as_bytes()).collect::<Vec<Result<i32, _>>>();
+    assert_eq!(read("\u{feff}+1, -2\r\n\n 3\n+4"), vec![Ok(1), Ok(-2), Ok(3), Ok(4)]);
+    assert_eq!(read("1\n2,,x\n"),
+               vec![Ok(1), Ok(2), Err("line 2, bytes 2..2: empty token: \"\"".to_string()),
+                    Err("line 2, bytes 3..4: unknown format: \"x\"".to_string())]);
+}
@@ -0,0 +1,242 @@
+// Checks that solve_part2_naive, solve_part2_fast, solve_part2_fast_parallel and solve_streaming
+// agree on thousands of random inputs. The inputs deliberately include negative, zero and positive
+// totals, and inputs with no solution, since the modulo/multiple arithmetic in solve_part2_fast is
+// where sign bugs hide.
+
+extern crate common;
+extern crate day01;
+
+use common::property;
+use common::rng::Rng;
+use day01::FirstVisits;
+use day01::Frequency;
+use day01::Repeat;
+use day01::Solution;
+use day01::Visit;
+use day01::repeats;
+use day01::solve_part2_fast;
+use day01::solve_part2_fast_parallel;
+use day01::solve_part2_naive;
+use day01::solve_streaming;
+use std::collections::HashMap;
+use std::ops::Neg;
+
+// Short inputs with small values, so that the naive solver needs at most a few thousand passes.
+fn generate(rng: &mut Rng) -> Vec<i32> {
+    let len = rng.range(0, 12) as usize;
+    let max = *rng.choose(&[3, 10, 100]);
+    let mut numbers: Vec<i32> = (0..len).map(|_| rng.range(-max, max) as i32).collect();
+    if rng.chance(1, 4) {
+        // Force a zero total.
+        let total: i32 = numbers.iter().sum();
+        numbers.push(-total);
+    }
+    numbers
+}
+
+fn shrink(numbers: &[i32]) -> Vec<Vec<i32>> {
+    property::shrink_vec(numbers, |&x| {
+        property::shrink_i64(x as i64).into_iter().map(|y| y as i32).collect()
+    })
+}
+
+fn naive_matches_fast<T: Frequency>(numbers: &[T]) -> Result<(), String> {
+    let naive = solve_part2_naive(numbers);
+    let fast = solve_part2_fast(numbers);
+    let streaming = solve_streaming(numbers.iter().cloned()).map(|answers| answers.1);
+    let parallel = solve_part2_fast_parallel(numbers, 3);
+    if naive != fast {
+        Err(format!("naive returned {:?}, fast returned {:?}", naive, fast))
+    } else if parallel != fast {
+        Err(format!("parallel returned {:?}, fast returned {:?}", parallel, fast))
+    } else if streaming != fast {
+        Err(format!("streaming returned {:?}, fast returned {:?}", streaming, fast))
+    } else {
+        Ok(())
+    }
+}
+
+#[test]
+fn test_naive_matches_fast() {
+    property::check(2018, 5000, generate, |numbers: &Vec<i32>| shrink(numbers),
+                    |numbers: &Vec<i32>| naive_matches_fast(numbers));
+}
+
+// Simulates `passes` passes, and returns every repeat on the way, in order.
+fn simulate_repeats(numbers: &[i32], passes: i32) -> Vec<Repeat<i32>> {
+    let mut first_visits: HashMap<i32, (i32, i32)> = HashMap::new();
+    first_visits.insert(0, (-1, 0));
+    let mut repeated: Vec<Repeat<i32>> = Vec::new();
+    let mut sum = 0;
+    for k in 0..passes {
+        for (j, &number) in numbers.iter().enumerate() {
+            sum += number;
+            match first_visits.get(&sum) {
+                Some(&(i, ki)) => {
+                    if !repeated.iter().any(|repeat| repeat.value == sum) {
+                        repeated.push(Repeat{value: sum, i, ki, j: j as i32, k});
+                    }
+                },
+                None => {
+                    first_visits.insert(sum, (j as i32, k));
+                },
+            }
+        }
+    }
+    repeated
+}
+
+fn repeats_match_simulation(numbers: &[i32]) -> Result<(), String> {
+    let passes = 30;
+    let simulated = simulate_repeats(numbers, passes);
+    let found: Vec<Repeat<i32>> = repeats(numbers).unwrap()
+        .map(Result::unwrap)
+        .take_while(|repeat| repeat.k < passes)
+        .collect();
+    if found == simulated {
+        Ok(())
+    } else {
+        Err(format!("repeats() returned {:?}, simulation returned {:?}", found, simulated))
+    }
+}
+
+#[test]
+fn test_repeats_match_simulation() {
+    property::check(2018, 1000, generate, |numbers: &Vec<i32>| shrink(numbers),
+                    |numbers: &Vec<i32>| repeats_match_simulation(numbers));
+}
+
+#[test]
+fn test_first_repeat_matches_fast() {
+    property::check(2018, 5000, generate, |numbers: &Vec<i32>| shrink(numbers), |numbers| {
+        let first = repeats(numbers).unwrap().next().map(Result::unwrap)
+            .map(|repeat| Solution{value: repeat.value, i: repeat.i, j: repeat.j, k: repeat.k});
+        let fast = solve_part2_fast(numbers).unwrap();
+        if first == fast {
+            Ok(())
+        } else {
+            Err(format!("repeats() started with {:?}, fast returned {:?}", first, fast))
+        }
+    });
+}
+
+// Checks FirstVisits against a simulation of the first 30 passes, for every target the simulation
+// reaches and a few that it does not.
+fn first_visits_match_simulation(&(start, ref numbers): &(i32, Vec<i32>)) -> Result<(), String> {
+    let passes = 30;
+    let mut simulated: HashMap<i32, Visit<i32>> = HashMap::new();
+    simulated.insert(start, Visit{j: -1, k: 0});
+    let mut frequency = start;
+    for k in 0..passes {
+        for (j, &number) in numbers.iter().enumerate() {
+            frequency += number;
+            simulated.entry(frequency).or_insert(Visit{j: j as i32, k});
+        }
+    }
+    let first_visits = FirstVisits::new(numbers, start).unwrap();
+    for target in (start - 200)..(start + 200) {
+        let found = first_visits.first_visit(target).unwrap();
+        let expected = simulated.get(&target);
+        let matches = match (expected, found.as_ref()) {
+            (Some(expected), found) => Some(expected) == found,
+            // Not reached in the simulation, so it must take more passes, or never happen.
+            (None, Some(visit)) => visit.k >= passes,
+            (None, None) => true,
+        };
+        if !matches {
+            return Err(format!("target {}: first_visit() returned {:?}, simulation returned {:?}",
+                               target, found, expected));
+        }
+    }
+    Ok(())
+}
+
+#[test]
+fn test_first_visits_match_simulation() {
+    let generate_with_start = |rng: &mut Rng| (rng.range(-50, 50) as i32, generate(rng));
+    let shrink_with_start = |&(start, ref numbers): &(i32, Vec<i32>)| {
+        let mut candidates: Vec<(i32, Vec<i32>)> = shrink(numbers).into_iter()
+            .map(|numbers| (start, numbers))
+            .collect();
+        if start != 0 {
+            candidates.push((0, numbers.clone()));
+        }
+        candidates
+    };
+    property::check(2018, 1000, generate_with_start, shrink_with_start,
+                    first_visits_match_simulation);
+}
+
+// Pushes the prefix sums of a small input up against one end of the integer range: `base`, then
+// the small input, then `-base` to bring the total back down. Far enough from the end that the
+// naive solver does not overflow while walking past the last candidate.
+fn with_base<T>(base: T, numbers: &[i32]) -> Vec<T> where T: Copy + From<i32> + Neg<Output = T> {
+    let mut based = vec![base];
+    based.extend(numbers.iter().map(|&x| T::from(x)));
+    based.push(-base);
+    based
+}
+
+fn generate_extreme(rng: &mut Rng) -> (bool, u16, Vec<i32>) {
+    (rng.chance(1, 2), 10_000 + rng.below(10_000) as u16, generate(rng))
+}
+
+fn shrink_extreme(&(near_max, margin, ref numbers): &(bool, u16, Vec<i32>))
+        -> Vec<(bool, u16, Vec<i32>)> {
+    shrink(numbers).into_iter().map(|numbers| (near_max, margin, numbers)).collect()
+}
+
+#[test]
+fn test_naive_matches_fast_at_extremes() {
+    property::check(2018, 2000, generate_extreme, shrink_extreme, |&(near_max, margin, ref v)| {
+        let base = if near_max {
+            i64::MAX - i64::from(margin)
+        } else {
+            i64::MIN + i64::from(margin)
+        };
+        naive_matches_fast(&with_base(base, v))
+    });
+    property::check(2018, 2000, generate_extreme, shrink_extreme, |&(near_max, margin, ref v)| {
+        let base = if near_max {
+            i128::MAX - i128::from(margin)
+        } else {
+            i128::MIN + i128::from(margin)
+        };
+        naive_matches_fast(&with_base(base, v))
+    });
+}
+
+// The total is ±1, so the multiples span almost the whole integer range.
+#[test]
+fn test_fast_with_unit_total_at_extremes() {
+    let numbers = [i64::MAX - 5, 3, -(i64::MAX - 5) - 2];
+    let solution = Some(Solution{value: i64::MAX - 2, i: 1, j: 0, k: 3});
+    assert_eq!(solve_part2_naive(&numbers), Ok(solution.clone()));
+    assert_eq!(solve_part2_fast(&numbers), Ok(solution));
+    let numbers = [i64::MIN + 5, -3, -(i64::MIN + 5) + 2];
+    let solution = Some(Solution{value: i64::MIN + 2, i: 1, j: 0, k: 3});
+    assert_eq!(solve_part2_naive(&numbers), Ok(solution.clone()));
+    assert_eq!(solve_part2_fast(&numbers), Ok(solution));
+}
+
+// Makes sure the generator actually covers the interesting cases.
+#[test]
+fn test_generator_coverage() {
+    let mut rng = Rng::new(2018);
+    let (mut negative, mut zero, mut positive, mut no_solution) = (0, 0, 0, 0);
+    for _ in 0..5000 {
+        let numbers = generate(&mut rng);
+        let total: i32 = numbers.iter().sum();
+        match total {
+            t if t < 0 => negative += 1,
+            0 => zero += 1,
+            _ => positive += 1,
+        }
+        if solve_part2_fast(&numbers).unwrap().is_none() {
+            no_solution += 1;
+        }
+    }
+    assert!(negative > 500 && zero > 500 && positive > 500 && no_solution > 100,
+            "negative={} zero={} positive={} no_solution={}",
+            negative, zero, positive, no_solution);
+}
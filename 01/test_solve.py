@@ -1,9 +1,33 @@
+import json
+import os
+
 import nose
 
+from solve import parse_input
+from solve import soln_less_than
 from solve import solve_part1
 from solve import solve_part2
 from solve import solve_part2_naive
 
+VECTORS_DIR = os.path.join(os.path.dirname(os.path.abspath(__file__)), "vectors")
+
+
+def test_parse_input():
+    nose.tools.assert_equal(parse_input("+1\n-2\n+3\n"), [1, -2, 3])
+    nose.tools.assert_equal(parse_input("+1, -2, +3, +1"), [1, -2, 3, 1])
+    nose.tools.assert_equal(parse_input("1,-2,3\n\n+4 5\t-6\n7 , 8"),
+                            [1, -2, 3, 4, 5, -6, 7, 8])
+    for text, message in [("1,,2", "line 1: empty token"),
+                          ("1\n3, ", "line 2: empty token"),
+                          ("1-2", "line 1: ambiguous sign"),
+                          ("+ 3", "line 1: ambiguous sign"),
+                          ("+-4", "line 1: ambiguous sign"),
+                          ("x", "line 1: not a number")]:
+        with nose.tools.assert_raises(ValueError) as context:
+            parse_input(text)
+        nose.tools.assert_true(str(context.exception).startswith(message),
+                               str(context.exception))
+
 
 def test_part1():
     nose.tools.assert_equal(solve_part1([1, 1, 1]), 3)
@@ -24,3 +48,32 @@ def test_part2_naive():
     check_part2([7, 7, -2, -7, -4], (14, 1, 2, 2))
     check_part2([50, 50, -99], (50, 0, 2, 49))
     check_part2([2, 2, 2, 7, -3], None)
+    check_part2([0, 1], (0, -1, 0, 0))
+    check_part2([1, 1, -1, 5], (1, 0, 2, 0))
+    check_part2([-2, 3], (0, -1, 0, 2))
+
+
+# The vector files are shared with the Rust tests, so that both implementations
+# are held to the same answers and the same tie-breaking.
+def load_vectors(name):
+    with open(os.path.join(VECTORS_DIR, name)) as f:
+        return [json.loads(line) for line in f if line.strip()]
+
+
+def soln_from_json(soln):
+    if soln is None:
+        return None
+    return (soln["value"], soln["i"], soln["j"], soln["k"])
+
+
+def test_part2_vectors():
+    for vector in load_vectors("part2.jsonl"):
+        check_part2(vector["numbers"], soln_from_json(vector["expected"]))
+
+
+def test_soln_less_than_vectors():
+    for vector in load_vectors("better_than.jsonl"):
+        nose.tools.assert_equal(
+            soln_less_than(soln_from_json(vector["a"]),
+                           soln_from_json(vector["b"])),
+            vector["a_better"])
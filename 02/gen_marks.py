@@ -0,0 +1,60 @@
+#!/usr/bin/env python3
+"""
+Prints solve/src/marks.rs: the ranges of chars whose general category is Mn,
+Me or Mc (nonspacing, enclosing and spacing combining marks), which grapheme
+mode joins to the char before them.
+
+Usage (from the repository root):
+    python3 02/gen_marks.py > 02/solve/src/marks.rs
+
+The table follows the Unicode version of Python's unicodedata module, so use
+a recent Python 3; Python 2.7's is Unicode 5.2.
+"""
+
+import sys
+import unicodedata
+
+MARK_CATEGORIES = ("Mn", "Me", "Mc")
+
+
+def mark_ranges():
+    ranges = []
+    for code in range(sys.maxunicode + 1):
+        if unicodedata.category(chr(code)) not in MARK_CATEGORIES:
+            continue
+        if ranges and ranges[-1][1] == code - 1:
+            ranges[-1][1] = code
+        else:
+            ranges.append([code, code])
+    return ranges
+
+
+def main():
+    print("// Generated by 02/gen_marks.py from Unicode %s; do not edit."
+          % unicodedata.unidata_version)
+    print("//")
+    print("// The chars whose general category is Mn, Me or Mc, i.e. the combining marks, as")
+    print("// sorted, disjoint, inclusive ranges.")
+    print("")
+    print("use std::cmp::Ordering;")
+    print("")
+    print("const MARKS: &[(char, char)] = &[")
+    for first, last in mark_ranges():
+        print("    ('\\u{%x}', '\\u{%x}')," % (first, last))
+    print("];")
+    print("")
+    print("pub fn is_mark(c: char) -> bool {")
+    print("    MARKS.binary_search_by(|&(first, last)| {")
+    print("        if last < c {")
+    print("            Ordering::Less")
+    print("        } else if first > c {")
+    print("            Ordering::Greater")
+    print("        } else {")
+    print("            Ordering::Equal")
+    print("        }")
+    print("    }).is_ok()")
+    print("}")
+
+
+if __name__ == "__main__":
+    main()
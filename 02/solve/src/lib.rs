@@ -0,0 +1,749 @@
+// Comments lack context; see puzzle text for more info.
+
+extern crate common;
+
+mod marks;
+mod segmentation;
+
+pub use segmentation::Segmentation;
+
+use common::Json;
+use common::ParseError;
+use common::Solver;
+use common::ToJson;
+use common::Variant;
+use common::input;
+use std::collections::HashMap;
+use std::hash::BuildHasherDefault;
+use std::hash::Hash;
+use std::hash::Hasher;
+use std::hint::black_box;
+use std::ops::Range;
+
+// Returns (num_2, num_3, num_2 * num_3), counting chars.
+// For the function type, see https://stackoverflow.com/a/41180422/744071.
+pub fn solve_part1<T: AsRef<str>>(ids: &[T]) -> (i32, i32, i32) {
+    solve_part1_with_segmentation(ids, Segmentation::Chars)
+}
+
+// As above, counting the given units.
+pub fn solve_part1_with_segmentation<T: AsRef<str>>(ids: &[T], segmentation: Segmentation)
+        -> (i32, i32, i32) {
+    let mut num_repeats_to_count : HashMap<i32, i32> = HashMap::new();
+    for id in ids {
+        let mut char_to_count : HashMap<&str, i32> = HashMap::new();
+        for c in segmentation.split(id.as_ref()) {
+            *(char_to_count.entry(c).or_insert(0)) += 1;
+        }
+        let mut num_repeats: Vec<i32> = char_to_count.values().copied().collect();
+        num_repeats.sort_unstable();
+        num_repeats.dedup();
+        for count in num_repeats.iter() {
+            *(num_repeats_to_count.entry(*count).or_insert(0)) += 1;
+        }
+        // println!("id={} char_to_count={:?} num_repeats={:?}",
+        //     id.as_ref(), char_to_count, num_repeats);
+        // println!("num_repeats_to_count={:?}", num_repeats_to_count);
+    }
+    let num_2 = *(num_repeats_to_count.get(&2).unwrap_or(&0));
+    let num_3 = *(num_repeats_to_count.get(&3).unwrap_or(&0));
+    (num_2, num_3, num_2 * num_3)
+}
+
+#[test]
+fn test_part1() {
+    assert_eq!(
+        solve_part1(&["abcdef", "bababc", "abbcde", "abcccd", "aabcdd", "abcdee", "ababab"]),
+        (4, 3, 12));
+    // Two of "\u{e9}", and three of "\u{65e5}".
+    assert_eq!(solve_part1(&["a\u{e9}\u{e9}", "\u{65e5}\u{65e5}\u{65e5}b", "abc"]), (1, 1, 1));
+    // As chars, "e\u{301}" is an "e" and a combining acute accent, so this has three "e"s.
+    let ids = ["ee\u{301}e\u{301}", "\u{e9}e\u{301}"];
+    assert_eq!(solve_part1(&ids), (1, 1, 1));
+    assert_eq!(solve_part1_with_segmentation(&ids, Segmentation::Graphemes), (1, 0, 0));
+    // Arabic meem with a fatha, then with a damma: two different clusters, but the same letter.
+    let ids = ["\u{645}\u{64e}\u{645}\u{64f}"];
+    assert_eq!(solve_part1(&ids), (1, 0, 0));
+    assert_eq!(solve_part1_with_segmentation(&ids, Segmentation::Graphemes), (0, 0, 0));
+}
+
+// Cuts each ID into units and gives every unit a code, equal for equal units: chars are their
+// own code, and grapheme clusters are numbered in order of appearance.
+fn encode<T: AsRef<str>>(ids: &[T], segmentation: Segmentation) -> Vec<Vec<u32>> {
+    match segmentation {
+        Segmentation::Chars =>
+            ids.iter().map(|id| id.as_ref().chars().map(u32::from).collect()).collect(),
+        Segmentation::Graphemes => {
+            let mut unit_to_code: HashMap<&str, u32> = HashMap::new();
+            ids.iter()
+                .map(|id| segmentation.split(id.as_ref()).into_iter()
+                     .map(|unit| {
+                         let next_code = unit_to_code.len() as u32;
+                         *unit_to_code.entry(unit).or_insert(next_code)
+                     })
+                     .collect())
+                .collect()
+        },
+    }
+}
+
+// The answer to part 2. Interpretation: ids[index_a] and ids[index_b] (with index_a < index_b)
+// are id_a and id_b, which are equal apart from one unit, as given by `difference`; or which are
+// equal outright (exact duplicates) if `difference` is None.
+#[derive(Clone, Debug, PartialEq)]
+pub struct MatchingIds {
+    pub index_a: usize,
+    pub index_b: usize,
+    pub id_a: String,
+    pub id_b: String,
+    pub segmentation: Segmentation,
+    pub difference: Option<Difference>,
+}
+
+// Where two matching IDs differ: char_a and char_b at `position`. The position counts units of
+// the segmentation, and char_a and char_b are such units, e.g. a grapheme cluster of several
+// chars. When insertions and deletions match too, one of char_a and char_b can be empty: that ID
+// is the other one with the unit at `position` left out.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Difference {
+    pub position: usize,
+    pub char_a: String,
+    pub char_b: String,
+}
+
+impl MatchingIds {
+    fn new<T: AsRef<str>>(ids: &[T], segmentation: Segmentation, index_a: usize, index_b: usize,
+                          position: usize) -> MatchingIds {
+        let id_a = ids[index_a].as_ref().to_string();
+        let id_b = ids[index_b].as_ref().to_string();
+        let difference = if id_a == id_b {
+            None
+        } else {
+            let units_a = segmentation.split(&id_a);
+            let units_b = segmentation.split(&id_b);
+            // The shorter ID, if any, has nothing at `position`.
+            let char_a = if units_a.len() < units_b.len() { "" } else { units_a[position] };
+            let char_b = if units_b.len() < units_a.len() { "" } else { units_b[position] };
+            Some(Difference{position, char_a: char_a.to_string(), char_b: char_b.to_string()})
+        };
+        MatchingIds{index_a, index_b, id_a, id_b, segmentation, difference}
+    }
+
+    // The letters the two IDs have in common, i.e. the longer one (or either one, if they are the
+    // same length) without the differing unit.
+    pub fn common(&self) -> String {
+        match self.difference {
+            None => self.id_a.clone(),
+            Some(ref difference) => {
+                let longer = if difference.char_a.is_empty() { &self.id_b } else { &self.id_a };
+                let mut units = self.segmentation.split(longer);
+                units.remove(difference.position);
+                units.concat()
+            },
+        }
+    }
+}
+
+impl ToJson for MatchingIds {
+    fn to_json(&self) -> Json {
+        Json::object(vec![
+            ("common", self.common().to_json()),
+            ("index_a", self.index_a.to_json()),
+            ("index_b", self.index_b.to_json()),
+            ("id_a", self.id_a.to_json()),
+            ("id_b", self.id_b.to_json()),
+            // All three are null for exact duplicates.
+            ("position", self.difference.as_ref().map(|d| d.position).to_json()),
+            ("char_a", self.difference.as_ref().map(|d| d.char_a.clone()).to_json()),
+            ("char_b", self.difference.as_ref().map(|d| d.char_b.clone()).to_json())])
+    }
+}
+
+// Returns the matching pair, comparing chars. Only IDs of the same length can match.
+// O(num_strings * string_length^2). Did not spend any time looking for a more efficient solution.
+pub fn solve_part2_naive<T: AsRef<str>>(ids: &[T]) -> Option<MatchingIds> {
+    naive_matching_ids(ids, Segmentation::Chars, false)
+}
+
+// As above, comparing the given units.
+pub fn solve_part2_naive_with_segmentation<T: AsRef<str>>(ids: &[T], segmentation: Segmentation)
+        -> Option<MatchingIds> {
+    naive_matching_ids(ids, segmentation, false)
+}
+
+// As above, but an ID also matches an ID one unit shorter that it equals with one unit deleted.
+pub fn solve_part2_naive_with_indels<T: AsRef<str>>(ids: &[T], segmentation: Segmentation)
+        -> Option<MatchingIds> {
+    naive_matching_ids(ids, segmentation, true)
+}
+
+// An encoded ID with at most one unit left out, as the units `prefix` followed by `suffix`, so
+// that the naive solver can hash and compare it without building it.
+struct Without<'a> {
+    prefix: &'a [u32],
+    suffix: &'a [u32],
+}
+
+impl<'a> Without<'a> {
+    fn units(&self) -> impl Iterator<Item = &'a u32> {
+        self.prefix.iter().chain(self.suffix)
+    }
+}
+
+impl<'a> Hash for Without<'a> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        state.write_usize(self.prefix.len() + self.suffix.len());
+        for &unit in self.units() {
+            state.write_u32(unit);
+        }
+    }
+}
+
+impl<'a> PartialEq for Without<'a> {
+    fn eq(&self, other: &Without<'a>) -> bool {
+        self.prefix.len() + self.suffix.len() == other.prefix.len() + other.suffix.len() &&
+            self.units().eq(other.units())
+    }
+}
+
+impl<'a> Eq for Without<'a> {}
+
+// A multiply-rotate hash (as in Firefox's FxHash), one unit per step. With SipHash, the default,
+// hashing took most of the naive solver's time.
+#[derive(Default)]
+struct UnitHasher(u64);
+
+impl Hasher for UnitHasher {
+    fn finish(&self) -> u64 {
+        self.0
+    }
+
+    fn write(&mut self, bytes: &[u8]) {
+        for &byte in bytes {
+            self.write_u64(u64::from(byte));
+        }
+    }
+
+    fn write_u32(&mut self, n: u32) {
+        self.write_u64(u64::from(n));
+    }
+
+    fn write_u64(&mut self, n: u64) {
+        self.0 = (self.0.rotate_left(5) ^ n).wrapping_mul(0x51_7c_c1_b7_27_22_0a_95);
+    }
+
+    fn write_usize(&mut self, n: usize) {
+        self.write_u64(n as u64);
+    }
+}
+
+type UnitMap<'a> = HashMap<Without<'a>, usize, BuildHasherDefault<UnitHasher>>;
+
+// For each index to drop, takes the IDs long enough in order, and compares each one without that
+// unit first with the earlier IDs of its length without it, and then, if `indels`, with all of
+// the IDs one unit shorter. Returns the first match.
+fn naive_matching_ids<T: AsRef<str>>(ids: &[T], segmentation: Segmentation, indels: bool)
+        -> Option<MatchingIds> {
+    let units: Vec<Vec<u32>> = encode(ids, segmentation);
+    let max_len = units.iter().map(|id| id.len()).max().unwrap_or(0);
+    let mut first_index: UnitMap = UnitMap::default();
+    for (i, id) in units.iter().enumerate().rev() {
+        first_index.insert(Without{prefix: id, suffix: &[]}, i);
+    }
+    for index_to_drop in 0..max_len {
+        let mut seen: UnitMap = UnitMap::default();
+        for (i, id) in units.iter().enumerate() {
+            if id.len() <= index_to_drop {
+                continue;
+            }
+            let mod_id = Without{prefix: &id[..index_to_drop], suffix: &id[index_to_drop + 1..]};
+            if let Some(&other) = seen.get(&mod_id) {
+                return Some(MatchingIds::new(ids, segmentation, other, i, index_to_drop));
+            }
+            if indels {
+                if let Some(&other) = first_index.get(&mod_id) {
+                    let (a, b) = (other.min(i), other.max(i));
+                    return Some(MatchingIds::new(ids, segmentation, a, b, index_to_drop));
+                }
+            }
+            seen.insert(mod_id, i);
+        }
+    }
+    None
+}
+
+// Polynomial hashes modulo the Mersenne prime 2^61 - 1, which makes the reduction cheap and
+// collisions vanishingly rare without any randomness. The base is bigger than any unit's code, so
+// that two different IDs of the same length only collide through the reduction.
+const HASH_MODULUS: u64 = (1 << 61) - 1;
+const HASH_BASE: u64 = (1 << 32) + 15;
+
+fn hash_mul(a: u64, b: u64) -> u64 {
+    let product = u128::from(a) * u128::from(b);
+    let sum = (product & u128::from(HASH_MODULUS)) + (product >> 61);
+    (sum as u64) % HASH_MODULUS
+}
+
+fn hash_add(a: u64, b: u64) -> u64 {
+    (a + b) % HASH_MODULUS
+}
+
+fn hash_sub(a: u64, b: u64) -> u64 {
+    (a + HASH_MODULUS - b) % HASH_MODULUS
+}
+
+// Returns true iff a and b are equal apart from the unit at `index`.
+fn equal_without(a: &[u32], b: &[u32], index: usize) -> bool {
+    a[..index] == b[..index] && a[index + 1..] == b[index + 1..]
+}
+
+// Returns true iff `longer` without the unit at `index` is `shorter`.
+fn equal_after_deleting(longer: &[u32], index: usize, shorter: &[u32]) -> bool {
+    longer.len() == shorter.len() + 1 &&
+        longer[..index] == shorter[..index] && longer[index + 1..] == shorter[index..]
+}
+
+// As solve_part2_naive(), but in O(num_strings * string_length) expected time. For each index to
+// drop, the hash of every ID without that unit is hash(prefix) * base^len(suffix) + hash(suffix),
+// where the prefix hashes are built up one unit per index, and the suffix hash comes from the
+// prefix hash one unit on and the hash of the whole ID. Hashes only match for IDs of the same
+// length, and equal hashes are compared unit by unit, so a collision cannot give a wrong answer.
+// Returns the same answer as solve_part2_naive(), i.e. the first pair found when dropping index 0,
+// then 1, and so on.
+pub fn solve_part2<T: AsRef<str>>(ids: &[T]) -> Option<MatchingIds> {
+    find_matching_ids(ids, Segmentation::Chars, false)
+}
+
+// As above, comparing the given units.
+pub fn solve_part2_with_segmentation<T: AsRef<str>>(ids: &[T], segmentation: Segmentation)
+        -> Option<MatchingIds> {
+    find_matching_ids(ids, segmentation, false)
+}
+
+// As above, but an ID also matches an ID one unit shorter that it equals with one unit deleted,
+// i.e. the same answer as solve_part2_naive_with_indels(). An ID without the unit at an index is
+// looked up among the hashes of the whole IDs, too.
+pub fn solve_part2_with_indels<T: AsRef<str>>(ids: &[T], segmentation: Segmentation)
+        -> Option<MatchingIds> {
+    find_matching_ids(ids, segmentation, true)
+}
+
+fn find_matching_ids<T: AsRef<str>>(ids: &[T], segmentation: Segmentation, indels: bool)
+        -> Option<MatchingIds> {
+    let original_ids = ids;
+    let ids: Vec<Vec<u32>> = encode(ids, segmentation);
+    let max_len = ids.iter().map(|id| id.len()).max().unwrap_or(0);
+
+    // powers[p] = base^p.
+    let mut powers: Vec<u64> = vec![1; max_len + 1];
+    for p in 1..=max_len {
+        powers[p] = hash_mul(powers[p - 1], HASH_BASE);
+    }
+    let whole_hashes: Vec<u64> = ids.iter()
+        .map(|id| id.iter().fold(0, |hash, &c| hash_add(hash_mul(hash, HASH_BASE), u64::from(c))))
+        .collect();
+    // Every ID, in order, by its length and hash, for matching an insertion or deletion.
+    let mut whole_ids: HashMap<(usize, u64), Vec<usize>> = HashMap::new();
+    if indels {
+        for (i, id) in ids.iter().enumerate() {
+            whole_ids.entry((id.len(), whole_hashes[i])).or_default().push(i);
+        }
+    }
+    // The hash of each ID's first `index_to_drop` units.
+    let mut prefix_hashes: Vec<u64> = vec![0; ids.len()];
+    // The IDs longer than `index_to_drop`, in order.
+    let mut long_enough: Vec<usize> = (0..ids.len()).collect();
+    // The first ID by the length and hash of what is left without `index_to_drop`.
+    let mut seen: HashMap<(usize, u64), usize> = HashMap::new();
+    // The IDs whose key was already taken by a different ID (a collision), by key.
+    let mut collisions: HashMap<(usize, u64), Vec<usize>> = HashMap::new();
+    for index_to_drop in 0..max_len {
+        long_enough.retain(|&i| ids[i].len() > index_to_drop);
+        seen.clear();
+        collisions.clear();
+        for &i in &long_enough {
+            let id = &ids[i];
+            let suffix_len = id.len() - index_to_drop - 1;
+            let prefix_hash = prefix_hashes[i];
+            let next_prefix_hash = hash_add(hash_mul(prefix_hash, HASH_BASE),
+                                            u64::from(id[index_to_drop]));
+            prefix_hashes[i] = next_prefix_hash;
+            let suffix_hash = hash_sub(whole_hashes[i],
+                                       hash_mul(next_prefix_hash, powers[suffix_len]));
+            let hash = hash_add(hash_mul(prefix_hash, powers[suffix_len]), suffix_hash);
+            let key = (id.len() - 1, hash);
+            let first = seen.get(&key).cloned();
+            let mut candidates = first.into_iter()
+                .chain(collisions.get(&key).into_iter().flatten().cloned());
+            let matches = |&other: &usize| equal_without(&ids[other], id, index_to_drop);
+            if let Some(other) = candidates.find(matches) {
+                return Some(MatchingIds::new(original_ids, segmentation, other, i, index_to_drop));
+            }
+            if indels {
+                let shorter = whole_ids.get(&key).into_iter().flatten()
+                    .find(|&&other| equal_after_deleting(id, index_to_drop, &ids[other]));
+                if let Some(&other) = shorter {
+                    let (a, b) = (other.min(i), other.max(i));
+                    return Some(MatchingIds::new(original_ids, segmentation, a, b, index_to_drop));
+                }
+            }
+            match first {
+                Some(_) => collisions.entry(key).or_default().push(i),
+                None => {
+                    seen.insert(key, i);
+                },
+            }
+        }
+    }
+    None
+}
+
+// The position, char_a and char_b of a match that is not an exact duplicate.
+#[cfg(test)]
+fn difference(found: &MatchingIds) -> (usize, &str, &str) {
+    let difference = found.difference.as_ref().unwrap();
+    (difference.position, &difference.char_a, &difference.char_b)
+}
+
+#[test]
+fn test_part2() {
+    let ids = ["abcde", "fghij", "klmno", "pqrst", "fguij", "axcye", "wvxyz"];
+    let expected = MatchingIds{index_a: 1, index_b: 4, id_a: "fghij".to_string(),
+                               id_b: "fguij".to_string(), segmentation: Segmentation::Chars,
+                               difference: Some(Difference{position: 2, char_a: "h".to_string(),
+                                                           char_b: "u".to_string()})};
+    assert_eq!(expected.common(), "fgij");
+    assert_eq!(solve_part2_naive(&ids), Some(expected.clone()));
+    assert_eq!(solve_part2(&ids), Some(expected));
+    let empty: [&str; 0] = [];
+    assert_eq!(solve_part2(&empty), None);
+    assert_eq!(solve_part2(&["ab", "cd"]), None);
+    // Two pairs; both solvers find the one at the lowest index first.
+    let ids = ["xbc", "abz", "ybc", "abc"];
+    let found = solve_part2(&ids).unwrap();
+    assert_eq!((found.index_a, found.index_b, found.common()), (0, 2, "bc".to_string()));
+    assert_eq!(solve_part2_naive(&ids), Some(found));
+}
+
+#[test]
+fn test_part2_duplicates() {
+    let ids = ["ab", "cd", "ab"];
+    let expected = MatchingIds{index_a: 0, index_b: 2, id_a: "ab".to_string(),
+                               id_b: "ab".to_string(), segmentation: Segmentation::Chars,
+                               difference: None};
+    assert_eq!(expected.common(), "ab");
+    assert_eq!(expected.to_json().to_string(),
+               "{\"common\":\"ab\",\"index_a\":0,\"index_b\":2,\"id_a\":\"ab\",\"id_b\":\"ab\",\
+                \"position\":null,\"char_a\":null,\"char_b\":null}");
+    assert_eq!(solve_part2_naive(&ids), Some(expected.clone()));
+    assert_eq!(solve_part2(&ids), Some(expected.clone()));
+    assert_eq!(solve_part2_with_indels(&ids, Segmentation::Chars), Some(expected));
+    let ids = ["e\u{301}x", "e\u{301}x"];
+    let found = solve_part2_with_segmentation(&ids, Segmentation::Graphemes).unwrap();
+    assert_eq!((found.difference.clone(), found.common()), (None, "e\u{301}x".to_string()));
+}
+
+#[test]
+fn test_part2_unicode() {
+    // Multi-byte chars; the byte lengths differ.
+    let ids = ["na\u{ef}ve", "\u{65e5}\u{672c}\u{8a9e}!!", "naive"];
+    let found = solve_part2(&ids).unwrap();
+    assert_eq!((found.index_a, found.index_b, difference(&found).0), (0, 2, 2));
+    assert_eq!((difference(&found).1, difference(&found).2, found.common().as_str()),
+               ("\u{ef}", "i", "nave"));
+    assert_eq!(solve_part2_naive(&ids), Some(found));
+
+    // The same accented letter as one char and as a letter and a combining mark. As chars, the
+    // flags are two regional indicators each, and differ in both.
+    let ids = ["caf\u{e9}\u{1f1eb}\u{1f1f7}", "cafe\u{301}\u{1f1e9}\u{1f1ea}",
+               "cafe\u{1f1eb}\u{1f1f7}"];
+    let found = solve_part2_with_segmentation(&ids, Segmentation::Graphemes).unwrap();
+    assert_eq!((found.index_a, found.index_b, difference(&found).0), (0, 2, 3));
+    assert_eq!((difference(&found).1, difference(&found).2, found.common().as_str()),
+               ("\u{e9}", "e", "caf\u{1f1eb}\u{1f1f7}"));
+    assert_eq!(solve_part2_naive_with_segmentation(&ids, Segmentation::Graphemes), Some(found));
+    let ids = ["cafe\u{301}\u{1f1eb}\u{1f1f7}", "cafe\u{1f1eb}\u{1f1f7}"];
+    let found = solve_part2_with_segmentation(&ids, Segmentation::Graphemes).unwrap();
+    assert_eq!((difference(&found).1, difference(&found).2), ("e\u{301}", "e"));
+    // Arabic with harakat: as chars, only the marks differ; as grapheme clusters, whole letters.
+    let ids = ["\u{645}\u{64e}\u{631}", "\u{645}\u{64f}\u{631}"];
+    assert_eq!(solve_part2(&ids).map(|found| found.common()), Some("\u{645}\u{631}".to_string()));
+    let found = solve_part2_with_segmentation(&ids, Segmentation::Graphemes).unwrap();
+    assert_eq!(difference(&found),
+               (0, "\u{645}\u{64e}", "\u{645}\u{64f}"));
+    assert_eq!(found.common(), "\u{631}");
+    let ids = ["x\u{1f1eb}\u{1f1f7}", "x\u{1f1e9}\u{1f1ea}"];
+    assert_eq!(solve_part2(&ids), None);
+    let found = solve_part2_with_segmentation(&ids, Segmentation::Graphemes).unwrap();
+    assert_eq!((difference(&found).0, found.common().as_str()), (1, "x"));
+}
+
+#[test]
+fn test_part2_matches_naive() {
+    use common::rng::Rng;
+    let mut rng = Rng::new(2018);
+    for _ in 0..500 {
+        // Few letters and short IDs, so that near-duplicates (and full duplicates) are common.
+        let id_len = rng.range(1, 6) as usize;
+        let ids: Vec<String> = (0..rng.range(0, 30))
+            .map(|_| (0..id_len).map(|_| ['a', '\u{e9}', '\u{65e5}'][rng.below(3) as usize])
+                 .collect())
+            .collect();
+        assert_eq!(solve_part2(&ids), solve_part2_naive(&ids), "{:?}", ids);
+
+        // Grapheme clusters of one to three chars.
+        let units = ["a", "e\u{301}", "\u{1f1eb}\u{1f1f7}", "\u{e9}"];
+        let ids: Vec<String> = (0..rng.range(0, 30))
+            .map(|_| (0..id_len).map(|_| units[rng.below(4) as usize]).collect())
+            .collect();
+        assert_eq!(solve_part2_with_segmentation(&ids, Segmentation::Graphemes),
+                   solve_part2_naive_with_segmentation(&ids, Segmentation::Graphemes),
+                   "{:?}", ids);
+
+        // Mixed lengths.
+        let ids: Vec<String> = (0..rng.range(0, 30))
+            .map(|_| (0..rng.range(0, 5)).map(|_| units[rng.below(3) as usize]).collect())
+            .collect();
+        for &segmentation in &[Segmentation::Chars, Segmentation::Graphemes] {
+            assert_eq!(solve_part2_with_segmentation(&ids, segmentation),
+                       solve_part2_naive_with_segmentation(&ids, segmentation),
+                       "{:?}", ids);
+            let found = solve_part2_with_indels(&ids, segmentation);
+            assert_eq!(found, solve_part2_naive_with_indels(&ids, segmentation), "{:?}", ids);
+            // After a deletion, what is left is the shorter ID.
+            if let Some(found) = found {
+                match found.difference {
+                    Some(ref d) if d.char_a.is_empty() => assert_eq!(found.common(), found.id_a),
+                    Some(ref d) if d.char_b.is_empty() => assert_eq!(found.common(), found.id_b),
+                    _ => {},
+                }
+            }
+        }
+    }
+}
+
+#[test]
+fn test_part2_mixed_lengths() {
+    let ids = ["abcd", "xyz", "abed", "xyw", "abd"];
+    let found = solve_part2(&ids).unwrap();
+    assert_eq!((found.index_a, found.index_b, difference(&found).0), (0, 2, 2));
+    assert_eq!(solve_part2(&["abcd", "abd", ""]), None);
+
+    // Dropping index 1 from "abcd" leaves "acd", which is not an ID, but dropping index 2 leaves
+    // "abd".
+    let found = solve_part2_with_indels(&["abcd", "abd", "xyz"], Segmentation::Chars).unwrap();
+    assert_eq!(found, MatchingIds{
+        index_a: 0, index_b: 1, id_a: "abcd".to_string(), id_b: "abd".to_string(),
+        segmentation: Segmentation::Chars,
+        difference: Some(Difference{position: 2, char_a: "c".to_string(),
+                                    char_b: "".to_string()})});
+    assert_eq!(found.common(), "abd");
+    // An insertion, from the earlier ID to the later one.
+    let found = solve_part2_with_indels(&["abd", "xyz", "abcd"], Segmentation::Chars).unwrap();
+    assert_eq!((found.index_a, found.index_b, difference(&found).0), (0, 2, 2));
+    assert_eq!((difference(&found).1, difference(&found).2, found.common().as_str()),
+               ("", "c", "abd"));
+    // The empty ID is one deletion away from any ID of one unit.
+    let found = solve_part2_with_indels(&["", "\u{e9}"], Segmentation::Chars).unwrap();
+    assert_eq!((difference(&found).0, found.common().as_str()), (0, ""));
+    let found = solve_part2_with_indels(&["e\u{301}", "e"], Segmentation::Chars).unwrap();
+    assert_eq!((difference(&found).0, difference(&found).1), (1, "\u{301}"));
+    // As grapheme clusters, the same IDs are a substitution.
+    let found = solve_part2_with_indels(&["e\u{301}", "e"], Segmentation::Graphemes).unwrap();
+    assert_eq!(difference(&found),
+               (0, "e\u{301}", "e"));
+}
+
+// Interpretation: ids[a] and ids[b] (with a < b) differ at `positions` only, and `common` is what
+// they have in common, i.e. either one with those positions left out. Positions count units, as
+// in MatchingIds.
+#[derive(Clone, Debug, PartialEq)]
+pub struct NearPair {
+    pub a: usize,
+    pub b: usize,
+    pub positions: Vec<usize>,
+    pub common: String,
+}
+
+// Returns the positions at which a and b differ, or None if there are more than k.
+fn differing_positions(a: &[u32], b: &[u32], k: usize) -> Option<Vec<usize>> {
+    let mut positions: Vec<usize> = Vec::new();
+    for (p, (x, y)) in a.iter().zip(b).enumerate() {
+        if x != y {
+            if positions.len() == k {
+                return None;
+            }
+            positions.push(p);
+        }
+    }
+    Some(positions)
+}
+
+// Returns every pair of IDs of the same length that differ in at most k chars, ordered by (a, b).
+//
+// By the pigeonhole principle, if the positions are cut into k + 1 blocks, two IDs that differ in
+// at most k positions are equal on at least one block. So only IDs that share a block are compared,
+// and each pair is reported for the first block it shares. This is fast as long as few IDs share
+// a block by chance, i.e. when the blocks are long enough; for k close to the ID length, it comes
+// down to comparing all pairs.
+pub fn pairs_within_distance<T: AsRef<str>>(ids: &[T], k: usize) -> Vec<NearPair> {
+    pairs_within_distance_with_segmentation(ids, k, Segmentation::Chars)
+}
+
+// As above, comparing the given units.
+pub fn pairs_within_distance_with_segmentation<T: AsRef<str>>(
+        ids: &[T], k: usize, segmentation: Segmentation) -> Vec<NearPair> {
+    let original_ids = ids;
+    let ids: Vec<Vec<u32>> = encode(ids, segmentation);
+    let num_blocks = k + 1;
+    // The positions of block b of an ID of length id_len.
+    let block = |id_len: usize, b: usize| -> Range<usize> {
+        b * id_len / num_blocks..(b + 1) * id_len / num_blocks
+    };
+
+    let mut pairs: Vec<NearPair> = Vec::new();
+    for block_index in 0..num_blocks {
+        // IDs of different lengths never match, so the length is part of the key.
+        let mut block_to_ids: HashMap<(usize, &[u32]), Vec<usize>> = HashMap::new();
+        for (i, id) in ids.iter().enumerate() {
+            block_to_ids.entry((id.len(), &id[block(id.len(), block_index)])).or_default().push(i);
+        }
+        for group in block_to_ids.values() {
+            for (t, &a) in group.iter().enumerate() {
+                for &b in &group[t + 1..] {
+                    let shares = |earlier: usize| {
+                        let positions = block(ids[a].len(), earlier);
+                        ids[a][positions.clone()] == ids[b][positions]
+                    };
+                    if (0..block_index).any(shares) {
+                        continue;
+                    }
+                    if let Some(positions) = differing_positions(&ids[a], &ids[b], k) {
+                        let common: String = segmentation.split(original_ids[a].as_ref())
+                            .into_iter().enumerate()
+                            .filter(|&(p, _)| !positions.contains(&p))
+                            .map(|(_, unit)| unit)
+                            .collect();
+                        pairs.push(NearPair{a, b, positions, common});
+                    }
+                }
+            }
+        }
+    }
+    pairs.sort_unstable_by_key(|pair| (pair.a, pair.b));
+    pairs
+}
+
+#[test]
+fn test_pairs_within_distance() {
+    let ids = ["abcde", "fghij", "klmno", "pqrst", "fguij", "axcye", "wvxyz"];
+    assert_eq!(pairs_within_distance(&ids, 1),
+               vec![NearPair{a: 1, b: 4, positions: vec![2], common: "fgij".to_string()}]);
+    assert_eq!(pairs_within_distance(&ids, 2),
+               vec![NearPair{a: 0, b: 5, positions: vec![1, 3], common: "ace".to_string()},
+                    NearPair{a: 1, b: 4, positions: vec![2], common: "fgij".to_string()}]);
+    assert_eq!(pairs_within_distance(&["ab", "ab"], 0),
+               vec![NearPair{a: 0, b: 1, positions: vec![], common: "ab".to_string()}]);
+    // More blocks than positions.
+    assert_eq!(pairs_within_distance(&["ab", "cd", "ad"], 5).len(), 3);
+    let empty: [&str; 0] = [];
+    assert_eq!(pairs_within_distance(&empty, 2), vec![]);
+
+    let ids = ["\u{e9}t\u{e9}", "\u{65e5}\u{65e5}\u{65e5}", "\u{e9}te"];
+    assert_eq!(pairs_within_distance(&ids, 1),
+               vec![NearPair{a: 0, b: 2, positions: vec![2], common: "\u{e9}t".to_string()}]);
+    // IDs are not normalized, so "e\u{301}" and "\u{e9}" are different clusters.
+    let ids = ["e\u{301}te\u{301}", "ete\u{301}", "\u{e9}te"];
+    assert_eq!(pairs_within_distance_with_segmentation(&ids, 1, Segmentation::Graphemes),
+               vec![NearPair{a: 0, b: 1, positions: vec![0], common: "te\u{301}".to_string()}]);
+}
+
+#[test]
+fn test_pairs_within_distance_matches_all_pairs() {
+    use common::rng::Rng;
+    let mut rng = Rng::new(2018);
+    for _ in 0..300 {
+        let id_len = rng.range(0, 8) as usize;
+        let k = rng.range(0, 4) as usize;
+        // Mostly IDs of one length, and some one longer.
+        let ids: Vec<String> = (0..rng.range(0, 25))
+            .map(|_| (0..id_len + (rng.below(4) == 0) as usize)
+                 .map(|_| (b'a' + rng.below(3) as u8) as char)
+                 .collect())
+            .collect();
+        let mut expected: Vec<(usize, usize)> = Vec::new();
+        for a in 0..ids.len() {
+            for b in a + 1..ids.len() {
+                if ids[a].len() != ids[b].len() {
+                    continue;
+                }
+                let distance = ids[a].bytes().zip(ids[b].bytes()).filter(|(x, y)| x != y).count();
+                if distance <= k {
+                    expected.push((a, b));
+                }
+            }
+        }
+        let found: Vec<(usize, usize)> = pairs_within_distance(&ids, k).iter()
+            .map(|pair| (pair.a, pair.b))
+            .collect();
+        assert_eq!(found, expected, "{:?} with k = {}", ids, k);
+    }
+}
+
+// Parses one box ID per line.
+pub fn parse_input(input: &str) -> Vec<String> {
+    input::lines(input).skip_blank().map(|line| line.text.trim().to_string()).collect()
+}
+
+// The answer to part 1, with names for the tuple returned by solve_part1.
+#[derive(Debug)]
+#[derive(PartialEq)]
+pub struct Checksum {
+    pub num_2: i32,
+    pub num_3: i32,
+    pub checksum: i32,
+}
+
+impl ToJson for Checksum {
+    fn to_json(&self) -> Json {
+        Json::object(vec![
+            ("num_2", self.num_2.to_json()),
+            ("num_3", self.num_3.to_json()),
+            ("checksum", self.checksum.to_json())])
+    }
+}
+
+pub struct Day02;
+
+impl Solver for Day02 {
+    type Input = Vec<String>;
+    type Output1 = Checksum;
+    type Output2 = Option<MatchingIds>;
+
+    fn parse(input: &str) -> Result<Vec<String>, Vec<ParseError>> {
+        Ok(parse_input(input))
+    }
+
+    fn part1(input: &Vec<String>) -> Result<Checksum, String> {
+        let (num_2, num_3, checksum) = solve_part1(input);
+        Ok(Checksum{num_2, num_3, checksum})
+    }
+
+    fn part2(input: &Vec<String>) -> Result<Option<MatchingIds>, String> {
+        Ok(solve_part2(input))
+    }
+
+    fn variants() -> Vec<Variant<Vec<String>>> {
+        vec![
+            Variant{name: "solve_part1", part: 1,
+                    run: |input| { black_box(solve_part1(input)); }},
+            Variant{name: "solve_part2_naive", part: 2,
+                    run: |input| { black_box(solve_part2_naive(input)); }},
+            Variant{name: "solve_part2", part: 2,
+                    run: |input| { black_box(solve_part2(input)); }},
+            Variant{name: "solve_part2_with_indels", part: 2,
+                    run: |input| {
+                        black_box(solve_part2_with_indels(input, Segmentation::Chars));
+                    }}]
+    }
+}
@@ -0,0 +1,127 @@
+// How an ID is cut into the units that the solvers count and compare. An accented letter can be
+// one char ("é", U+00E9) or a letter and a combining mark ("e" and U+0301), and some emoji are
+// several chars, so the same text can be a different number of chars; as grapheme clusters, it
+// is what a reader sees as one character either way.
+//
+// Grapheme clusters follow the rules of Unicode Standard Annex #29 that matter for IDs (std only,
+// since the build machines cannot fetch unicode-segmentation): CR LF, combining marks, emoji
+// modifiers and tags, emoji joined with ZWJ, and pairs of regional indicators (flags). Combining
+// marks are all chars of general category Mn, Me or Mc (see marks.rs): that is Extend and
+// SpacingMark up to a handful of exceptions, e.g. Thai SARA AM, and takes in Hebrew points, Arabic
+// harakat, Indic vowel signs and viramas, and Thai vowel and tone marks. Hangul jamo sequences,
+// prepended characters and Indic conjuncts (a virama followed by a consonant) are not joined, so
+// e.g. "\u{915}\u{94d}\u{937}" is two clusters, as it was before Unicode 15.1.
+
+use super::marks::is_mark;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Segmentation {
+    Chars,
+    Graphemes,
+}
+
+impl Segmentation {
+    // Cuts `id` into units, which concatenate back to `id`.
+    pub fn split(self, id: &str) -> Vec<&str> {
+        match self {
+            Segmentation::Chars =>
+                id.char_indices().map(|(i, c)| &id[i..i + c.len_utf8()]).collect(),
+            Segmentation::Graphemes => graphemes(id),
+        }
+    }
+}
+
+const ZWJ: char = '\u{200d}';
+
+// Combining marks (which include the variation selectors), and the chars outside the M categories
+// that extend a cluster anyway: ZWNJ, halfwidth katakana voiced sound marks, emoji modifiers and
+// tags.
+fn is_extend(c: char) -> bool {
+    is_mark(c) ||
+        matches!(c, '\u{200c}' | '\u{ff9e}'..='\u{ff9f}' | '\u{1f3fb}'..='\u{1f3ff}' |
+                    '\u{e0020}'..='\u{e007f}')
+}
+
+fn is_pictographic(c: char) -> bool {
+    matches!(c,
+        '\u{a9}' | '\u{ae}' | '\u{203c}' | '\u{2049}' | '\u{2122}' | '\u{2139}' |
+        '\u{2194}'..='\u{2199}' | '\u{21a9}'..='\u{21aa}' | '\u{231a}'..='\u{23ff}' |
+        '\u{24c2}' | '\u{25aa}'..='\u{27bf}' | '\u{2934}'..='\u{2935}' |
+        '\u{2b05}'..='\u{2b55}' | '\u{3030}' | '\u{303d}' | '\u{3297}' | '\u{3299}' |
+        '\u{1f000}'..='\u{1f1e5}' | '\u{1f200}'..='\u{1f3fa}' | '\u{1f400}'..='\u{1faff}')
+}
+
+fn is_regional_indicator(c: char) -> bool {
+    matches!(c, '\u{1f1e6}'..='\u{1f1ff}')
+}
+
+fn graphemes(s: &str) -> Vec<&str> {
+    let mut clusters: Vec<&str> = Vec::new();
+    let mut start = 0;
+    let mut prev: Option<char> = None;
+    // Whether the chars so far end in a pictographic char followed by zero or more extenders, and
+    // whether the ZWJ just before this char came after such a run.
+    let mut after_pictographic = false;
+    let mut zwj_after_pictographic = false;
+    // The number of regional indicators in a row just before this char.
+    let mut num_regional = 0;
+    for (i, c) in s.char_indices() {
+        let joins = match prev {
+            None => true,
+            Some('\r') => c == '\n',
+            Some(p) if p.is_control() || c.is_control() => false,
+            Some(_) if is_extend(c) || c == ZWJ => true,
+            Some(ZWJ) => zwj_after_pictographic && is_pictographic(c),
+            Some(p) if is_regional_indicator(p) && is_regional_indicator(c) =>
+                num_regional % 2 == 1,
+            Some(_) => false,
+        };
+        if !joins {
+            clusters.push(&s[start..i]);
+            start = i;
+        }
+        zwj_after_pictographic = c == ZWJ && after_pictographic;
+        after_pictographic = is_pictographic(c) || (after_pictographic && is_extend(c));
+        num_regional = if is_regional_indicator(c) { num_regional + 1 } else { 0 };
+        prev = Some(c);
+    }
+    if start < s.len() {
+        clusters.push(&s[start..]);
+    }
+    clusters
+}
+
+#[test]
+fn test_split() {
+    let text = "e\u{301}\u{e9}\u{1f1eb}\u{1f1f7}\u{1f1e9}\u{1f1ea}\u{1f1ee}\
+                \u{1f44d}\u{1f3fd}\u{1f468}\u{200d}\u{1f469}\u{200d}\u{1f467}\
+                a\u{200d}b\r\n\n\u{301}";
+    assert_eq!(Segmentation::Graphemes.split(text),
+               vec!["e\u{301}", "\u{e9}", "\u{1f1eb}\u{1f1f7}", "\u{1f1e9}\u{1f1ea}", "\u{1f1ee}",
+                    "\u{1f44d}\u{1f3fd}", "\u{1f468}\u{200d}\u{1f469}\u{200d}\u{1f467}",
+                    "a\u{200d}", "b", "\r\n", "\n", "\u{301}"]);
+    assert_eq!(Segmentation::Chars.split("e\u{301}\u{65e5}"), vec!["e", "\u{301}", "\u{65e5}"]);
+    assert_eq!(Segmentation::Graphemes.split(""), Vec::<&str>::new());
+}
+
+#[test]
+fn test_split_non_latin_marks() {
+    let graphemes = |text| Segmentation::Graphemes.split(text);
+    // Hebrew "shalom", with points.
+    assert_eq!(graphemes("\u{5e9}\u{5b8}\u{5c1}\u{5dc}\u{5d5}\u{5b9}\u{5dd}"),
+               vec!["\u{5e9}\u{5b8}\u{5c1}", "\u{5dc}", "\u{5d5}\u{5b9}", "\u{5dd}"]);
+    // Arabic "marhaban", with harakat.
+    assert_eq!(graphemes("\u{645}\u{64e}\u{631}\u{652}\u{62d}\u{64e}\u{628}\u{64b}\u{627}"),
+               vec!["\u{645}\u{64e}", "\u{631}\u{652}", "\u{62d}\u{64e}", "\u{628}\u{64b}",
+                    "\u{627}"]);
+    // Devanagari "namaste": a virama (U+094D) and a vowel sign (U+0947, a spacing mark). And a
+    // nukta (U+093C).
+    assert_eq!(graphemes("\u{928}\u{92e}\u{938}\u{94d}\u{924}\u{947}\u{915}\u{93c}"),
+               vec!["\u{928}", "\u{92e}", "\u{938}\u{94d}", "\u{924}\u{947}", "\u{915}\u{93c}"]);
+    // Thai "thi ni": vowel and tone marks.
+    assert_eq!(graphemes("\u{e17}\u{e35}\u{e48}\u{e19}\u{e35}\u{e48}\u{e01}\u{e31}\u{e47}"),
+               vec!["\u{e17}\u{e35}\u{e48}", "\u{e19}\u{e35}\u{e48}", "\u{e01}\u{e31}\u{e47}"]);
+    // Halfwidth katakana with a voiced sound mark, and a variation selector.
+    assert_eq!(graphemes("\u{ff76}\u{ff9e}\u{2764}\u{fe0f}"),
+               vec!["\u{ff76}\u{ff9e}", "\u{2764}\u{fe0f}"]);
+}
@@ -0,0 +1,190 @@
+#[macro_use] extern crate lazy_static;
+extern crate common;
+extern crate regex;
+
+use common::ParseError;
+use common::Solver;
+use common::Variant;
+use common::parse;
+use common::parse::ParseErrorKind;
+use regex::Regex;
+use std::collections::HashSet;
+use std::hint::black_box;
+
+#[derive(Debug)]
+#[derive(PartialEq)]
+pub struct Claim {
+    pub id: i32,
+    pub start_row: usize,
+    pub start_col: usize,
+    pub num_rows: usize,
+    pub num_cols: usize,
+}
+
+// Parses a claim from a string.
+pub fn parse_claim(claim_str: &str) -> Result<Claim, ParseError> {
+    lazy_static! {
+        static ref RE: Regex = Regex::new(concat!(
+            r"#(?P<id>\d+) @ (?P<start_col>\d+),(?P<start_row>\d+): ",
+            r"(?P<num_cols>\d+)x(?P<num_rows>\d+)")).unwrap();
+    }
+    // println!("re: {:?}", *RE);
+    // println!("claim_str: {}", claim_str);
+    // println!("caps: {:?}", RE.captures(claim_str));
+    let caps = RE.captures(claim_str).ok_or_else(|| ParseError::new(
+        ParseErrorKind::UnknownFormat, claim_str, parse::trimmed_span(claim_str)))?;
+    let span = |name: &str| caps.name(name).unwrap().range();
+    Ok(Claim{
+        id: parse::parse_int(claim_str, span("id"))?,
+        start_row: parse::parse_int(claim_str, span("start_row"))?,
+        start_col: parse::parse_int(claim_str, span("start_col"))?,
+        num_rows: parse::parse_int(claim_str, span("num_rows"))?,
+        num_cols: parse::parse_int(claim_str, span("num_cols"))?})
+}
+
+#[test]
+fn test_parse_claim() {
+    assert_eq!(parse_claim("#1 @ 2,3: 4x5"),
+               Ok(Claim{id: 1, start_col: 2, start_row: 3, num_cols: 4, num_rows: 5}));
+    assert_eq!(parse_claim("#123 @ 596,731: 11x27"),
+               Ok(Claim{id: 123, start_col: 596, start_row: 731, num_cols: 11, num_rows: 27}));
+    assert_eq!(parse_claim("Harrowdown Hill"),
+               Err(ParseError::new(ParseErrorKind::UnknownFormat, "Harrowdown Hill", 0..15)));
+    assert_eq!(parse_claim("#9999999999 @ 1,1: 1x1"),
+               Err(ParseError::new(ParseErrorKind::IntegerOverflow,
+                                   "#9999999999 @ 1,1: 1x1", 1..11)));
+}
+
+// Dumb simulation-based solution. Returns None if there are no claims.
+pub fn solve_part1_naive(claims: &[Claim]) -> Option<i32> {
+    // Paint the claims on the canvas.
+    let canvas_num_rows = claims.iter().map(|c| c.start_row + c.num_rows).max()? + 1;
+    let canvas_num_cols = claims.iter().map(|c| c.start_col + c.num_cols).max()? + 1;
+    let mut canvas: Vec<i32> = vec![0; canvas_num_rows * canvas_num_cols];  // row-major indexing
+    for claim in claims {
+        for r in 0..claim.num_rows {
+            for c in 0..claim.num_cols {
+                canvas[(claim.start_row + r) * canvas_num_cols + (claim.start_col + c)] += 1;
+            }
+        }
+    }
+
+    // // Print the canvas for debugging.
+    // for r in 0..canvas_num_rows {
+    //     for c in 0..canvas_num_cols {
+    //         print!("{}", canvas[r * canvas_num_cols + c]);
+    //     }
+    //     print!("\n");
+    // }
+
+    // Count the number of cells that are part of multiple claims.
+    let mut num_conflicts = 0;
+    for r in 0..canvas_num_rows {
+        for c in 0..canvas_num_cols {
+            if canvas[r * canvas_num_cols + c] > 1 {
+                num_conflicts += 1;
+            }
+        }
+    }
+    Some(num_conflicts)
+}
+
+#[test]
+fn test_part1() {
+    let claims = vec![
+        parse_claim("#1 @ 1,3: 4x4").unwrap(),
+        parse_claim("#2 @ 3,1: 4x4").unwrap(),
+        parse_claim("#3 @ 5,5: 2x2").unwrap()];
+    assert_eq!(solve_part1_naive(&claims), Some(4));
+    assert_eq!(solve_part1_naive(&[]), None);
+}
+
+// Dumb simulation-based solution. Returns ids of conflictless claims, or None if there are no
+// claims.
+pub fn solve_part2_naive(claims: &[Claim]) -> Option<Vec<i32>> {
+    // Keep track of which claims are conflictless thus far.
+    let mut conflictless_claims: HashSet<i32> = HashSet::new();
+
+    // Paint the claims on the canvas. Each cell holds the id of the most recent claim on that
+    // cell. This tells us which claim id to remove from conflictless_claims if we later see a
+    // conflict for that cell.
+    let canvas_num_rows = claims.iter().map(|c| c.start_row + c.num_rows).max()? + 1;
+    let canvas_num_cols = claims.iter().map(|c| c.start_col + c.num_cols).max()? + 1;
+    let mut canvas: Vec<i32> = vec![0; canvas_num_rows * canvas_num_cols];  // row-major indexing
+    for claim in claims {
+        let mut has_conflicts = false;
+        for r in 0..claim.num_rows {
+            for c in 0..claim.num_cols {
+                let index = (claim.start_row + r) * canvas_num_cols + (claim.start_col + c);
+                if canvas[index] != 0 {
+                    conflictless_claims.remove(&canvas[index]);
+                    has_conflicts = true;
+                }
+                canvas[index] = claim.id;
+            }
+        }
+        if !has_conflicts {
+            conflictless_claims.insert(claim.id);
+        }
+    }
+
+    Some(conflictless_claims.iter().copied().collect())
+}
+
+#[test]
+fn test_part2() {
+    let claims = vec![
+        parse_claim("#1 @ 1,3: 4x4").unwrap(),
+        parse_claim("#2 @ 3,1: 4x4").unwrap(),
+        parse_claim("#3 @ 5,5: 2x2").unwrap()];
+    assert_eq!(solve_part2_naive(&claims), Some(vec![3]));
+    assert_eq!(solve_part2_naive(&[]), None);
+}
+
+// Parses one claim per line.
+pub fn parse_input(input: &str) -> Result<Vec<Claim>, Vec<ParseError>> {
+    parse::parse_lines(input, parse_claim)
+}
+
+#[test]
+fn test_parse_input() {
+    assert_eq!(parse_input("\u{feff}#1 @ 2,3: 4x5\r\n#2 @ 3,1: 4x4\r\n"),
+               Ok(vec![Claim{id: 1, start_col: 2, start_row: 3, num_cols: 4, num_rows: 5},
+                       Claim{id: 2, start_col: 3, start_row: 1, num_cols: 4, num_rows: 4}]));
+    let input = "#1 @ 2,3: 4x5\nnope\n#3 @ 1,1: 1x99999999999999999999\n";
+    let errors = parse_input(input).unwrap_err();
+    assert_eq!(errors.iter().map(|e| (e.line, e.kind.clone())).collect::<Vec<_>>(),
+               vec![(2, ParseErrorKind::UnknownFormat), (3, ParseErrorKind::IntegerOverflow)]);
+}
+
+pub struct Day03;
+
+fn no_claims() -> String {
+    "no claims".to_string()
+}
+
+impl Solver for Day03 {
+    type Input = Vec<Claim>;
+    type Output1 = i32;
+    type Output2 = Vec<i32>;
+
+    fn parse(input: &str) -> Result<Vec<Claim>, Vec<ParseError>> {
+        parse_input(input)
+    }
+
+    fn part1(input: &Vec<Claim>) -> Result<i32, String> {
+        solve_part1_naive(input).ok_or_else(no_claims)
+    }
+
+    fn part2(input: &Vec<Claim>) -> Result<Vec<i32>, String> {
+        solve_part2_naive(input).ok_or_else(no_claims)
+    }
+
+    fn variants() -> Vec<Variant<Vec<Claim>>> {
+        vec![
+            Variant{name: "solve_part1_naive", part: 1,
+                    run: |input| { black_box(solve_part1_naive(input)); }},
+            Variant{name: "solve_part2_naive", part: 2,
+                    run: |input| { black_box(solve_part2_naive(input)); }}]
+    }
+}
@@ -0,0 +1,328 @@
+#[macro_use] extern crate lazy_static;
+extern crate common;
+extern crate regex;
+
+use common::Json;
+use common::ParseError;
+use common::Solver;
+use common::ToJson;
+use common::input;
+use common::parse;
+use common::parse::ParseErrorKind;
+use regex::Regex;
+use std::collections::HashMap;
+
+#[derive(Debug)]
+#[derive(Eq)]
+#[derive(PartialEq)]
+#[derive(Ord)]
+#[derive(PartialOrd)]
+pub enum Action {
+    WakesUp,
+    FallsAsleep,
+    BeginsShift,
+}
+
+#[derive(Debug)]
+#[derive(Eq)]
+#[derive(PartialEq)]
+#[derive(Ord)]
+#[derive(PartialOrd)]
+pub struct LogLine {
+    pub year: i32,
+    pub month: i32,
+    pub day: i32,
+    pub hour: i32,
+    pub minute: i32,
+    pub guard_id: Option<i32>,
+    pub action: Action,
+}
+
+// Parses a log line from a string.
+pub fn parse_log_line(line_str: &str) -> Result<LogLine, ParseError> {
+    lazy_static! {
+        static ref RE: Regex = Regex::new(concat!(
+            r"\[(\d{4})-(\d{2})-(\d{2}) (\d{2}):(\d{2})\] ",
+            r"(Guard #(\d+) begins shift|falls asleep|wakes up)")).unwrap();
+    }
+    // println!("re: {:?}", *RE);
+    // println!("line_str: {}", line_str);
+    // println!("captures: {:?}", RE.captures(line_str));
+    let captures = RE.captures(line_str).ok_or_else(|| ParseError::new(
+        ParseErrorKind::UnknownFormat, line_str, parse::trimmed_span(line_str)))?;
+    let span = |index: usize| captures.get(index).unwrap().range();
+    Ok(LogLine{
+        year: parse::parse_int(line_str, span(1))?,
+        month: parse::parse_int(line_str, span(2))?,
+        day: parse::parse_int(line_str, span(3))?,
+        hour: parse::parse_int(line_str, span(4))?,
+        minute: parse::parse_int(line_str, span(5))?,
+        guard_id: match captures.get(7) {
+            Some(m) => Some(parse::parse_int(line_str, m.range())?),
+            None => None,
+        },
+        action: if captures[6].contains("begins") {
+                    Action::BeginsShift
+                } else if captures[6].contains("falls") {
+                    Action::FallsAsleep
+                } else {
+                    Action::WakesUp
+                }
+        })
+}
+
+#[test]
+fn test_parse_log_line() {
+    assert_eq!(parse_log_line("[1518-11-01 23:58] Guard #99 begins shift"),
+               Ok(LogLine{year: 1518, month: 11, day: 1, hour: 23, minute: 58,
+                          guard_id: Some(99), action: Action::BeginsShift}));
+    assert_eq!(parse_log_line("[1518-11-02 00:40] falls asleep"),
+               Ok(LogLine{year: 1518, month: 11, day: 2, hour: 0, minute: 40,
+                          guard_id: None, action: Action::FallsAsleep}));
+    assert_eq!(parse_log_line("[1518-11-02 00:50] wakes up"),
+               Ok(LogLine{year: 1518, month: 11, day: 2, hour: 0, minute: 50,
+                          guard_id: None, action: Action::WakesUp}));
+    assert_eq!(parse_log_line("[1518-11-02 00:50] sleepwalks"),
+               Err(ParseError::new(ParseErrorKind::UnknownFormat,
+                                   "[1518-11-02 00:50] sleepwalks", 0..29)));
+    assert_eq!(parse_log_line("[1518-11-02 00:50] Guard #12345678901 begins shift"),
+               Err(ParseError::new(ParseErrorKind::IntegerOverflow,
+                                   "[1518-11-02 00:50] Guard #12345678901 begins shift", 26..37)));
+}
+
+// Returns a map of (guard_id, minute) to num_days_asleep. The log lines must be as returned by
+// parse_input(), which rejects the logs that would break the asserts below.
+pub fn get_sleep_stats(log_lines: &[LogLine]) -> HashMap<(i32, i32), i32> {
+    let mut result : HashMap<(i32, i32), i32> = HashMap::new();
+    let mut current_guard_id = None;
+    let mut asleep_since_minute = None;
+    for log_line in log_lines {
+        // println!("state: {:?} {:?} log_line: {:?}",
+        //     current_guard_id, asleep_since_minute, log_line);
+        match log_line.action {
+            Action::FallsAsleep => {
+                assert!(asleep_since_minute.is_none());  // inception-style sleep
+                asleep_since_minute = Some(log_line.minute);
+            },
+            Action::WakesUp => {
+                assert!(current_guard_id.is_some());
+                assert!(asleep_since_minute.is_some());
+                for minute in asleep_since_minute.unwrap()..log_line.minute {
+                    *(result.entry((current_guard_id.unwrap(), minute)).or_insert(0)) += 1;
+                }
+                asleep_since_minute = None;
+            },
+            Action::BeginsShift => {
+                assert!(asleep_since_minute.is_none());
+                current_guard_id = Some(log_line.guard_id.unwrap());
+            },
+        }
+    }
+    result
+}
+
+// Returns (guard_id, minute, guard_id * minute), or None if no guard ever falls asleep.
+pub fn solve_part1(log_lines: &[LogLine]) -> Option<(i32, i32, i32)> {
+    let stats = get_sleep_stats(log_lines);
+    let mut guard_id_to_minutes_asleep: HashMap<i32, i32> = HashMap::new();
+    for ((guard_id, _minute), num_days_asleep) in stats.iter() {
+        *(guard_id_to_minutes_asleep.entry(*guard_id).or_insert(0)) += num_days_asleep;
+    }
+    let chosen_guard_id = *(guard_id_to_minutes_asleep.iter()
+            .max_by_key(|e| e.1)?.0);
+
+    let mut minute_to_days_asleep : HashMap<i32, i32> = HashMap::new();
+    for ((guard_id, minute), num_days_asleep) in stats.iter() {
+        if *guard_id == chosen_guard_id {
+            *(minute_to_days_asleep.entry(*minute).or_insert(0)) += num_days_asleep;
+        }
+    }
+    let chosen_minute = *(minute_to_days_asleep.iter()
+            .max_by_key(|e| e.1)?.0);
+
+    Some((chosen_guard_id, chosen_minute, chosen_guard_id * chosen_minute))
+}
+
+#[cfg(test)]
+fn get_test_log_lines() -> Vec<LogLine> {
+    let lines = r"[1518-11-01 00:00] Guard #10 begins shift
+                  [1518-11-01 00:05] falls asleep
+                  [1518-11-01 00:25] wakes up
+                  [1518-11-01 00:30] falls asleep
+                  [1518-11-01 00:55] wakes up
+                  [1518-11-01 23:58] Guard #99 begins shift
+                  [1518-11-02 00:40] falls asleep
+                  [1518-11-02 00:50] wakes up
+                  [1518-11-03 00:05] Guard #10 begins shift
+                  [1518-11-03 00:24] falls asleep
+                  [1518-11-03 00:29] wakes up
+                  [1518-11-04 00:02] Guard #99 begins shift
+                  [1518-11-04 00:36] falls asleep
+                  [1518-11-04 00:46] wakes up
+                  [1518-11-05 00:03] Guard #99 begins shift
+                  [1518-11-05 00:45] falls asleep
+                  [1518-11-05 00:55] wakes up";
+    lines.split('\n')
+        .map(|line| parse_log_line(line.trim()).unwrap())
+        .collect()
+}
+
+#[test]
+fn test_solve_part1() {
+    let log_lines = get_test_log_lines();
+    assert_eq!(solve_part1(&log_lines), Some((10, 24, 240)));
+    assert_eq!(solve_part1(&log_lines[..1]), None);
+}
+
+// Returns (guard_id, minute, guard_id * minute), or None if no guard ever falls asleep.
+pub fn solve_part2(log_lines: &[LogLine]) -> Option<(i32, i32, i32)> {
+    let stats = get_sleep_stats(log_lines);
+    let (chosen_guard_id, chosen_minute) = *(stats.iter()
+            .max_by_key(|e| e.1)?.0);
+    Some((chosen_guard_id, chosen_minute, chosen_guard_id * chosen_minute))
+}
+
+#[test]
+fn test_solve_part2() {
+    let log_lines = get_test_log_lines();
+    assert_eq!(solve_part2(&log_lines), Some((99, 45, 4455)));
+    assert_eq!(solve_part2(&log_lines[..1]), None);
+}
+
+// Parses one log line per line, sorts them chronologically, and fills in guard_id. Every line
+// that comes before the first "begins shift" once sorted is an error, since no guard is on duty,
+// and so is every line that cannot follow the one before it: a guard falls asleep and then wakes
+// up before anything else happens.
+pub fn parse_input(input: &str) -> Result<Vec<LogLine>, Vec<ParseError>> {
+    let log_lines = parse::parse_lines(input, parse_log_line)?;
+    // parse_lines() keeps the order of the non-blank lines, so this pairs each with its line.
+    let mut log_lines: Vec<(LogLine, input::Line)> =
+        log_lines.into_iter().zip(input::lines(input).skip_blank()).collect();
+
+    // Sort log lines, and fill in guard_id.
+    log_lines.sort_unstable_by(|a, b| a.0.cmp(&b.0));
+    let mut current_guard_id = None;
+    let mut previous_action: Option<&Action> = None;
+    let mut errors: Vec<ParseError> = Vec::new();
+    for (log_line, line) in &mut log_lines {
+        let mut error = |kind| errors.push(
+            ParseError::new(kind, line.text, parse::trimmed_span(line.text)).at_line(line.number));
+        if log_line.guard_id.is_some() {
+            current_guard_id = log_line.guard_id;
+        } else if current_guard_id.is_some() {
+            log_line.guard_id = current_guard_id;
+        } else {
+            error(ParseErrorKind::MissingEarlier{what: "\"begins shift\" line"});
+        }
+        let unexpected_after = match (previous_action, &log_line.action) {
+            (Some(&Action::FallsAsleep), &Action::FallsAsleep) |
+            (Some(&Action::FallsAsleep), &Action::BeginsShift) => Some("\"falls asleep\" line"),
+            (Some(&Action::WakesUp), &Action::WakesUp) => Some("\"wakes up\" line"),
+            (Some(&Action::BeginsShift), &Action::WakesUp) => Some("\"begins shift\" line"),
+            _ => None,
+        };
+        if let Some(after) = unexpected_after {
+            error(ParseErrorKind::Unexpected{after});
+        }
+        previous_action = Some(&log_line.action);
+    }
+    if errors.is_empty() {
+        Ok(log_lines.into_iter().map(|(log_line, _)| log_line).collect())
+    } else {
+        Err(errors)
+    }
+}
+
+#[test]
+fn test_parse_input_no_guard() {
+    let input = "[1518-11-01 00:30] falls asleep\n\
+                 [1518-11-01 00:05] wakes up\n\
+                 [1518-11-01 00:10] Guard #10 begins shift\n";
+    let kind = ParseErrorKind::MissingEarlier{what: "\"begins shift\" line"};
+    let errors = parse_input(input).unwrap_err();
+    assert_eq!(errors, vec![
+        ParseError::new(kind, "[1518-11-01 00:05] wakes up", 0..27).at_line(2)]);
+    assert_eq!(errors[0].to_string(),
+               "line 2, bytes 0..27: no earlier \"begins shift\" line: \
+                \"[1518-11-01 00:05] wakes up\"");
+}
+
+#[test]
+fn test_parse_input_out_of_order() {
+    let input = "[1518-11-01 00:00] Guard #10 begins shift\n\
+                 [1518-11-01 00:05] wakes up\n\
+                 [1518-11-01 00:10] falls asleep\n\
+                 [1518-11-01 00:20] falls asleep\n\
+                 [1518-11-01 00:30] wakes up\n\
+                 [1518-11-01 00:40] wakes up\n\
+                 [1518-11-01 00:50] falls asleep\n\
+                 \n\
+                 [1518-11-02 00:00] Guard #99 begins shift\n";
+    let error = |after, text: &str, line| {
+        ParseError::new(ParseErrorKind::Unexpected{after}, text, 0..text.len()).at_line(line)
+    };
+    let errors = parse_input(input).unwrap_err();
+    assert_eq!(errors, vec![
+        error("\"begins shift\" line", "[1518-11-01 00:05] wakes up", 2),
+        error("\"falls asleep\" line", "[1518-11-01 00:20] falls asleep", 4),
+        error("\"wakes up\" line", "[1518-11-01 00:40] wakes up", 6),
+        error("\"falls asleep\" line", "[1518-11-02 00:00] Guard #99 begins shift", 9)]);
+    assert_eq!(errors[1].to_string(),
+               "line 4, bytes 0..31: unexpected right after \"falls asleep\" line: \
+                \"[1518-11-01 00:20] falls asleep\"");
+}
+
+#[test]
+fn test_no_naps() {
+    let log_lines = parse_input("[1518-11-01 00:00] Guard #10 begins shift\n").unwrap();
+    assert_eq!(Day04::part1(&log_lines), Err("no guard ever falls asleep".to_string()));
+    assert_eq!(Day04::part2(&log_lines), Err("no guard ever falls asleep".to_string()));
+}
+
+// The answer to either part, with names for the tuple returned by solve_part1 and solve_part2.
+#[derive(Debug)]
+#[derive(PartialEq)]
+pub struct GuardMinute {
+    pub guard_id: i32,
+    pub minute: i32,
+    pub product: i32,
+}
+
+impl GuardMinute {
+    fn from_tuple((guard_id, minute, product): (i32, i32, i32)) -> GuardMinute {
+        GuardMinute{guard_id, minute, product}
+    }
+}
+
+impl ToJson for GuardMinute {
+    fn to_json(&self) -> Json {
+        Json::object(vec![
+            ("guard_id", self.guard_id.to_json()),
+            ("minute", self.minute.to_json()),
+            ("product", self.product.to_json())])
+    }
+}
+
+pub struct Day04;
+
+fn no_naps() -> String {
+    "no guard ever falls asleep".to_string()
+}
+
+impl Solver for Day04 {
+    type Input = Vec<LogLine>;
+    type Output1 = GuardMinute;
+    type Output2 = GuardMinute;
+
+    fn parse(input: &str) -> Result<Vec<LogLine>, Vec<ParseError>> {
+        parse_input(input)
+    }
+
+    fn part1(input: &Vec<LogLine>) -> Result<GuardMinute, String> {
+        solve_part1(input).map(GuardMinute::from_tuple).ok_or_else(no_naps)
+    }
+
+    fn part2(input: &Vec<LogLine>) -> Result<GuardMinute, String> {
+        solve_part2(input).map(GuardMinute::from_tuple).ok_or_else(no_naps)
+    }
+}
@@ -0,0 +1,149 @@
+extern crate common;
+
+use common::Json;
+use common::ParseError;
+use common::Solver;
+use common::ToJson;
+use common::input;
+use std::collections::HashSet;
+use std::fmt;
+
+fn reduces(a: char, b: char) -> bool {
+    (a.is_lowercase() ^ b.is_lowercase()) &&
+    (a.to_lowercase().to_string() == b.to_lowercase().to_string())
+}
+
+pub fn solve_part1<T: AsRef<str>>(input: &T) -> String {
+    let mut stack: Vec<char> = Vec::new();
+    for c in input.as_ref().chars() {
+        if !stack.is_empty() && reduces(*stack.last().unwrap(), c) {
+            stack.pop();
+        } else {
+            stack.push(c);
+        }
+    }
+    stack.iter().collect()
+}
+
+#[test]
+fn test_solve_part1() {
+    assert_eq!(solve_part1(&"aA"), "".to_string());
+    assert_eq!(solve_part1(&"abBA"), "".to_string());
+    assert_eq!(solve_part1(&"abAB"), "abAB".to_string());
+    assert_eq!(solve_part1(&"aabAAB"), "aabAAB".to_string());
+    assert_eq!(solve_part1(&"dabAcCaCBAcCcaDA"), "dabCBAcaDA".to_string());
+}
+
+// Why solve_part2() has no answer.
+#[derive(Debug, PartialEq)]
+pub enum RemovalError {
+    // There are no units to remove.
+    EmptyPolymer,
+    // A unit whose lowercase is not a single char, e.g. 'İ' (lowercase "i\u{307}"), so it has no
+    // unit type to remove.
+    NoSingleLowercase(char),
+}
+
+impl fmt::Display for RemovalError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            RemovalError::EmptyPolymer => write!(f, "empty polymer"),
+            RemovalError::NoSingleLowercase(c) => {
+                write!(f, "{:?} does not lowercase to a single char", c)
+            },
+        }
+    }
+}
+
+// Returns c.to_lowercase() if it is a single char.
+fn single_lowercase(c: char) -> Result<char, RemovalError> {
+    let mut lower = c.to_lowercase();
+    match (lower.next(), lower.next()) {
+        (Some(c_lower), None) => Ok(c_lower),
+        _ => Err(RemovalError::NoSingleLowercase(c)),
+    }
+}
+
+// Returns (lowercase_char_to_remove, resulting_reduced_str).
+// Naive brute-force implementation.
+pub fn solve_part2<T: AsRef<str>>(input: &T) -> Result<(char, String), RemovalError> {
+    let mut lowercase_chars: HashSet<char> = HashSet::new();
+    for c in input.as_ref().chars() {
+        lowercase_chars.insert(single_lowercase(c)?);
+    }
+    // println!("Trying each of {} chars to remove...", lowercase_chars.len());
+    let mut best: Option<(char, String)> = None;
+    let mut best_reduced_str_len = input.as_ref().len();
+    for char_to_remove in lowercase_chars.iter() {
+        let mut stack: Vec<char> = Vec::new();
+        for c in input.as_ref().chars() {
+            if single_lowercase(c)? == *char_to_remove {
+                continue;
+            }
+            if !stack.is_empty() && reduces(*stack.last().unwrap(), c) {
+                stack.pop();
+            } else {
+                stack.push(c);
+            }
+        }
+        let reduced_str: String = stack.iter().collect();
+        let reduced_str_len = reduced_str.len();
+        // Removing a unit type always shortens the polymer, so a nonempty one gets an answer.
+        if reduced_str_len < best_reduced_str_len {
+            best = Some((*char_to_remove, reduced_str));
+            best_reduced_str_len = reduced_str_len;
+        }
+    }
+    best.ok_or(RemovalError::EmptyPolymer)
+}
+
+#[test]
+fn test_solve_part2() {
+    assert_eq!(solve_part2(&"dabAcCaCBAcCcaDA"), Ok(('c', "daDA".to_string())));
+    assert_eq!(solve_part2(&""), Err(RemovalError::EmptyPolymer));
+    assert_eq!(solve_part2(&"aA\u{130}"), Err(RemovalError::NoSingleLowercase('\u{130}')));
+    assert_eq!(Day05::part2(&"\u{130}".to_string()),
+               Err("'\u{130}' does not lowercase to a single char".to_string()));
+}
+
+// The input is a single polymer on the first non-blank line.
+pub fn parse_input(input: &str) -> String {
+    input::lines(input).skip_blank().next().map_or("", |line| line.text.trim()).to_string()
+}
+
+// The answer to part 2: which unit type to remove, and the length of the reduced polymer.
+#[derive(Debug)]
+#[derive(PartialEq)]
+pub struct Removal {
+    pub char_removed: char,
+    pub length: usize,
+}
+
+impl ToJson for Removal {
+    fn to_json(&self) -> Json {
+        Json::object(vec![
+            ("char_removed", self.char_removed.to_json()),
+            ("length", self.length.to_json())])
+    }
+}
+
+pub struct Day05;
+
+impl Solver for Day05 {
+    type Input = String;
+    type Output1 = usize;
+    type Output2 = Removal;
+
+    fn parse(input: &str) -> Result<String, Vec<ParseError>> {
+        Ok(parse_input(input))
+    }
+
+    fn part1(input: &String) -> Result<usize, String> {
+        Ok(solve_part1(input).len())
+    }
+
+    fn part2(input: &String) -> Result<Removal, String> {
+        let (char_removed, reduced_str) = solve_part2(input).map_err(|e| e.to_string())?;
+        Ok(Removal{char_removed, length: reduced_str.len()})
+    }
+}
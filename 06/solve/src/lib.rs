@@ -1,16 +1,25 @@
-use std::io;
+extern crate common;
+
+use common::ParseError;
+use common::Solver;
+use common::Variant;
+use common::parse;
+use common::parse::ParseErrorKind;
 use std::collections::HashSet;
+use std::hint::black_box;
 
-fn print_grid(grid: &Vec<(i32, i32)>, num_rows: i32, num_cols: i32) {
-    println!("=========================");
+// Returns the grid as text, one row per line, for debugging.
+pub fn format_grid(grid: &[(i32, i32)], num_rows: i32, num_cols: i32) -> String {
+    let mut text = String::from("=========================\n");
     for r in 0..num_rows {
         for c in 0..num_cols {
             let (i, d) = grid[(r * num_cols + c) as usize];
-            print!("(i={:3} d={:3})   ", i, d);
+            text += &format!("(i={:3} d={:3})   ", i, d);
         }
-        println!();
+        text += "\n";
     }
-    println!("=========================");
+    text += "=========================\n";
+    text
 }
 
 // Returns the size of the largest finite Voronoi cell (using Manhattan distance).
@@ -22,12 +31,14 @@ fn print_grid(grid: &Vec<(i32, i32)>, num_rows: i32, num_cols: i32) {
 // be colored i, because d(c', j) == d(c, j) + 1 for all j.
 //
 // Ugh, this turned out to be pretty hairy. Should've used even bruter force.
-fn solve_part1_naive(coords: &Vec<(i32, i32)>) -> i32 {
+//
+// Returns None if there are no coords.
+pub fn solve_part1_naive(coords: &[(i32, i32)]) -> Option<i32> {
     // Figure out the size of the grid.
-    let min_r = coords.iter().map(|p| p.0).min().unwrap();
-    let max_r = coords.iter().map(|p| p.0).max().unwrap();
-    let min_c = coords.iter().map(|p| p.1).min().unwrap();
-    let max_c = coords.iter().map(|p| p.1).max().unwrap();
+    let min_r = coords.iter().map(|p| p.0).min()?;
+    let max_r = coords.iter().map(|p| p.0).max()?;
+    let min_c = coords.iter().map(|p| p.1).min()?;
+    let max_c = coords.iter().map(|p| p.1).max()?;
     let num_rows = max_r - min_r + 1;
     let num_cols = max_c - min_c + 1;
     
@@ -46,8 +57,7 @@ fn solve_part1_naive(coords: &Vec<(i32, i32)>) -> i32 {
     grid.resize((num_rows * num_cols) as usize, (NOT_VISITED, -1));
 
     // Counts the number of cells that are closest to each input coordinate.
-    let mut index_to_area: Vec<i32> = Vec::new();
-    index_to_area.resize(coords.len(), 0);
+    let mut index_to_area: Vec<i32> = vec![0; coords.len()];
 
     // Holds the current distance, and the cells that would get that distance
     // in the next iteration.
@@ -111,7 +121,7 @@ fn solve_part1_naive(coords: &Vec<(i32, i32)>) -> i32 {
         }
         current_dist += 1;
 
-        // print_grid(&grid, num_rows, num_cols);
+        // print!("{}", format_grid(&grid, num_rows, num_cols));
     }
 
     // Exclude coordinates whose Voronoi cells are infinite.
@@ -129,33 +139,78 @@ fn solve_part1_naive(coords: &Vec<(i32, i32)>) -> i32 {
     let mut max_area: i32 = -1;
     for (index, area) in index_to_area.iter().enumerate() {
         let is_infinite = indices_with_infinite_cells.contains(&(index as i32));
-        println!("index_to_area[{}] = {} (infinite={})", index, area, is_infinite);
+        // println!("index_to_area[{}] = {} (infinite={})", index, area, is_infinite);
         if !is_infinite && *area > max_area {
             max_area = *area;
         }
     }
-    max_area
+    Some(max_area)
 }
 
 #[test]
 fn test_solve_part1_naive() {
     let coords: Vec<(i32, i32)> = [(1, 1), (1, 6), (8, 3), (3, 4), (5, 5), (8, 9)].to_vec();
-    assert_eq!(solve_part1_naive(&coords), 17);
+    assert_eq!(solve_part1_naive(&coords), Some(17));
+    assert_eq!(solve_part1_naive(&[]), None);
 }
 
-fn main() {
-    let mut coords: Vec<(i32, i32)> = Vec::new();
-    let mut line = String::new();
-    loop {
-        line.clear();
-        io::stdin().read_line(&mut line)
-            .expect("Failed to read line");
-        if line.is_empty() {
-            break;
-        }
-        let tmp: Vec<&str> = line.trim().split(", ").collect();
-        assert_eq!(tmp.len(), 2);
-        coords.push((tmp[0].parse().unwrap(), tmp[1].parse().unwrap()));
+// Parses a "row, col" coordinate from a string.
+pub fn parse_coord(line_str: &str) -> Result<(i32, i32), ParseError> {
+    let span = parse::trimmed_span(line_str);
+    let tmp: Vec<&str> = line_str[span.clone()].split(", ").collect();
+    if tmp.len() != 2 {
+        let kind = ParseErrorKind::WrongFieldCount{expected: 2, found: tmp.len()};
+        return Err(ParseError::new(kind, line_str, span));
     }
-    println!("part 1: {:?}", solve_part1_naive(&coords));
+    let r_span = span.start..(span.start + tmp[0].len());
+    let c_span = (r_span.end + 2)..span.end;
+    Ok((parse::parse_int(line_str, r_span)?, parse::parse_int(line_str, c_span)?))
+}
+
+#[test]
+fn test_parse_coord() {
+    assert_eq!(parse_coord(" 1, 6"), Ok((1, 6)));
+    assert_eq!(parse_coord("1, 6, 7"),
+               Err(ParseError::new(ParseErrorKind::WrongFieldCount{expected: 2, found: 3},
+                                   "1, 6, 7", 0..7)));
+    assert_eq!(parse_coord("1, x"),
+               Err(ParseError::new(ParseErrorKind::UnknownFormat, "1, x", 3..4)));
+}
+
+// Parses one coordinate per line.
+pub fn parse_input(input: &str) -> Result<Vec<(i32, i32)>, Vec<ParseError>> {
+    parse::parse_lines(input, parse_coord)
+}
+
+pub struct Day06;
+
+impl Solver for Day06 {
+    type Input = Vec<(i32, i32)>;
+    type Output1 = i32;
+    type Output2 = ();
+
+    const HAS_PART2: bool = false;
+
+    fn parse(input: &str) -> Result<Vec<(i32, i32)>, Vec<ParseError>> {
+        parse_input(input)
+    }
+
+    fn part1(input: &Vec<(i32, i32)>) -> Result<i32, String> {
+        solve_part1_naive(input).ok_or_else(|| "no coordinates".to_string())
+    }
+
+    fn part2(_input: &Vec<(i32, i32)>) -> Result<(), String> {
+        Err("day 06 part 2 is not implemented".to_string())
+    }
+
+    fn variants() -> Vec<Variant<Vec<(i32, i32)>>> {
+        vec![
+            Variant{name: "solve_part1_naive", part: 1,
+                    run: |input| { black_box(solve_part1_naive(input)); }}]
+    }
+}
+
+#[test]
+fn test_part2_not_implemented() {
+    assert_eq!(Day06::part2(&vec![(1, 1)]), Err("day 06 part 2 is not implemented".to_string()));
 }
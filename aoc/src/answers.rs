@@ -0,0 +1,170 @@
+// The answers manifest (answers.toml) records the known-good answer for each day and part of the
+// checked-in inputs. It looks like this:
+//
+//     [[answer]]
+//     day = "01"
+//     part = 2
+//     expected = '{"value":462,"i":364,"j":429,"k":144}'
+//
+// `expected` is the answer as printed by `aoc run --format json`. We only need this small subset
+// of TOML, so we parse it by hand rather than pulling in a crate.
+
+use std::fs;
+
+use common::input;
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct Answer {
+    pub day: String,
+    pub part: u32,
+    pub expected: String,
+}
+
+#[derive(Debug, PartialEq)]
+pub struct Answers {
+    answers: Vec<Answer>,
+}
+
+#[derive(Debug, PartialEq)]
+enum Value {
+    Integer(i64),
+    String(String),
+}
+
+// Parses a TOML integer, "basic string", or 'literal string'.
+fn parse_value(text: &str) -> Result<Value, String> {
+    if text.len() >= 2 && text.starts_with('\'') && text.ends_with('\'') {
+        return Ok(Value::String(text[1..text.len() - 1].to_string()));
+    }
+    if text.len() >= 2 && text.starts_with('"') && text.ends_with('"') {
+        let mut value = String::new();
+        let mut chars = text[1..text.len() - 1].chars();
+        while let Some(c) = chars.next() {
+            if c != '\\' {
+                value.push(c);
+                continue;
+            }
+            match chars.next() {
+                Some('"') => value.push('"'),
+                Some('\\') => value.push('\\'),
+                Some('n') => value.push('\n'),
+                Some('t') => value.push('\t'),
+                other => return Err(format!("unsupported escape: \\{}", other.unwrap_or(' '))),
+            }
+        }
+        return Ok(Value::String(value));
+    }
+    text.parse().map(Value::Integer).map_err(|_| format!("unsupported value: {}", text))
+}
+
+// Fields of the [[answer]] table currently being parsed.
+#[derive(Default)]
+struct PartialAnswer {
+    day: Option<String>,
+    part: Option<u32>,
+    expected: Option<String>,
+    line: usize,
+}
+
+impl PartialAnswer {
+    fn finish(self) -> Result<Answer, String> {
+        let missing = |key: &str| format!("answer at line {} is missing `{}`", self.line, key);
+        Ok(Answer{
+            day: self.day.clone().ok_or_else(|| missing("day"))?,
+            part: self.part.ok_or_else(|| missing("part"))?,
+            expected: self.expected.clone().ok_or_else(|| missing("expected"))?,
+        })
+    }
+}
+
+impl Answers {
+    pub fn parse(text: &str) -> Result<Answers, String> {
+        let mut answers: Vec<Answer> = Vec::new();
+        let mut current: Option<PartialAnswer> = None;
+        for line in input::lines(text) {
+            let error = |message: String| format!("line {}: {}", line.number, message);
+            let line_text = line.text.trim();
+            if line_text.is_empty() || line_text.starts_with('#') {
+                continue;
+            }
+            if line_text == "[[answer]]" {
+                if let Some(partial) = current.take() {
+                    answers.push(partial.finish()?);
+                }
+                current = Some(PartialAnswer{line: line.number, ..Default::default()});
+                continue;
+            }
+            let partial = current.as_mut()
+                .ok_or_else(|| error("expected [[answer]] first".to_string()))?;
+            let eq = line_text.find('=')
+                .ok_or_else(|| error(format!("expected `key = value`: {}", line_text)))?;
+            let key = line_text[..eq].trim();
+            let value = parse_value(line_text[eq + 1..].trim()).map_err(&error)?;
+            match (key, value) {
+                ("day", Value::String(day)) => partial.day = Some(day),
+                ("part", Value::Integer(part)) if part == 1 || part == 2 => {
+                    partial.part = Some(part as u32)
+                },
+                ("expected", Value::String(expected)) => partial.expected = Some(expected),
+                (key, value) => return Err(error(format!("unexpected {} = {:?}", key, value))),
+            }
+        }
+        if let Some(partial) = current.take() {
+            answers.push(partial.finish()?);
+        }
+        for (t, answer) in answers.iter().enumerate() {
+            if answers[..t].iter().any(|a| a.day == answer.day && a.part == answer.part) {
+                return Err(format!("duplicate answer for day {} part {}", answer.day, answer.part));
+            }
+        }
+        Ok(Answers{answers})
+    }
+
+    pub fn from_path(path: &str) -> Result<Answers, String> {
+        let text = fs::read_to_string(path).map_err(|e| format!("could not read {}: {}", path, e))?;
+        Answers::parse(&text).map_err(|e| format!("{}: {}", path, e))
+    }
+
+    // Returns the expected answer, as JSON text, or None if it has not been recorded.
+    pub fn get(&self, day: &str, part: u32) -> Option<&str> {
+        self.answers.iter()
+            .find(|answer| answer.day == day && answer.part == part)
+            .map(|answer| answer.expected.as_str())
+    }
+}
+
+// Returns the path of the manifest at the root of the workspace.
+pub fn default_path() -> String {
+    format!("{}/../answers.toml", env!("CARGO_MANIFEST_DIR"))
+}
+
+#[test]
+fn test_parse() {
+    let answers = Answers::parse(r#"
+        # Comment.
+        [[answer]]
+        day = "01"
+        part = 1
+        expected = '430'
+
+        [[answer]]
+        day = "02"
+        part = 2
+        expected = "\"fgij\""
+    "#).unwrap();
+    assert_eq!(answers.get("01", 1), Some("430"));
+    assert_eq!(answers.get("02", 2), Some("\"fgij\""));
+    assert_eq!(answers.get("01", 2), None);
+}
+
+#[test]
+fn test_parse_errors() {
+    assert_eq!(Answers::parse("day = \"01\""),
+               Err("line 1: expected [[answer]] first".to_string()));
+    assert_eq!(Answers::parse("[[answer]]\nday = \"01\"\npart = 1\n"),
+               Err("answer at line 1 is missing `expected`".to_string()));
+    assert_eq!(Answers::parse("[[answer]]\npart = 3\n"),
+               Err("line 2: unexpected part = Integer(3)".to_string()));
+    let duplicate = "[[answer]]\nday = \"01\"\npart = 1\nexpected = '1'\n".repeat(2);
+    assert_eq!(Answers::parse(&duplicate), Err("duplicate answer for day 01 part 1".to_string()));
+}
@@ -0,0 +1,136 @@
+// Times every registered variant of a day's solvers, e.g. day 01's solve_part2_naive against
+// solve_part2_fast. Std only, since the build machines cannot fetch criterion.
+
+use std::fmt;
+use std::time::Duration;
+use std::time::Instant;
+
+use common::Solver;
+
+use days;
+use days::SolverFn;
+
+#[derive(Debug, PartialEq)]
+pub struct Timing {
+    pub runs: usize,
+    pub min: Duration,
+    pub median: Duration,
+    pub max: Duration,
+}
+
+impl Timing {
+    pub fn from_samples(mut samples: Vec<Duration>) -> Timing {
+        assert!(!samples.is_empty());
+        samples.sort_unstable();
+        let n = samples.len();
+        let median = if n % 2 == 1 {
+            samples[n / 2]
+        } else {
+            (samples[n / 2 - 1] + samples[n / 2]) / 2
+        };
+        Timing{runs: n, min: samples[0], median, max: samples[n - 1]}
+    }
+}
+
+pub struct VariantReport {
+    pub day: String,
+    pub part: u32,
+    pub name: &'static str,
+    pub timing: Timing,
+    pub input_bytes: usize,
+}
+
+impl VariantReport {
+    // Input bytes processed per second, based on the median time.
+    pub fn throughput(&self) -> f64 {
+        self.input_bytes as f64 / self.timing.median.as_secs_f64()
+    }
+}
+
+impl fmt::Display for VariantReport {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "day {} part {} {}: min {:.2?}, median {:.2?}, max {:.2?} ({} runs, {:.2} MB/s)",
+               self.day, self.part, self.name, self.timing.min, self.timing.median,
+               self.timing.max, self.timing.runs, self.throughput() / 1e6)
+    }
+}
+
+struct BenchSolver<'a> {
+    input: &'a str,
+    part: Option<u32>,
+    runs: usize,
+    variant_names: &'a [String],
+}
+
+impl<'a> SolverFn for BenchSolver<'a> {
+    type Output = Result<Vec<VariantReport>, String>;
+
+    fn call<S: Solver>(self, day: &str) -> Result<Vec<VariantReport>, String> {
+        let parsed = S::parse(self.input)
+            .map_err(|errors| format!("{} parse errors", errors.len()))?;
+        let mut reports: Vec<VariantReport> = Vec::new();
+        for variant in S::variants() {
+            if self.part.is_some() && self.part != Some(variant.part) {
+                continue;
+            }
+            if !self.variant_names.is_empty()
+                    && !self.variant_names.iter().any(|name| name == variant.name) {
+                continue;
+            }
+            let samples: Vec<Duration> = (0..self.runs).map(|_| {
+                let start = Instant::now();
+                (variant.run)(&parsed);
+                start.elapsed()
+            }).collect();
+            reports.push(VariantReport{
+                day: day.to_string(),
+                part: variant.part,
+                name: variant.name,
+                timing: Timing::from_samples(samples),
+                input_bytes: self.input.len(),
+            });
+        }
+        Ok(reports)
+    }
+}
+
+// Runs each variant of the given day `runs` times on the given input. Parsing is not timed. Only
+// the variants named in `variant_names` are run, unless it is empty, e.g. to leave out a naive
+// solver that would take forever on a big input.
+pub fn bench_day(day: &str, input: &str, part: Option<u32>, runs: usize, variant_names: &[String])
+        -> Result<Vec<VariantReport>, String> {
+    if runs == 0 {
+        return Err("need at least one run".to_string());
+    }
+    let reports = days::dispatch(day, BenchSolver{input, part, runs, variant_names})?;
+    if reports.is_empty() && !variant_names.is_empty() {
+        return Err(format!("no variants named {}", variant_names.join(", ")));
+    }
+    Ok(reports)
+}
+
+#[test]
+fn test_timing_from_samples() {
+    let ms = Duration::from_millis;
+    assert_eq!(Timing::from_samples(vec![ms(3), ms(1), ms(2)]),
+               Timing{runs: 3, min: ms(1), median: ms(2), max: ms(3)});
+    assert_eq!(Timing::from_samples(vec![ms(4), ms(1), ms(2), ms(8)]),
+               Timing{runs: 4, min: ms(1), median: ms(3), max: ms(8)});
+}
+
+#[test]
+fn test_bench_day() {
+    let input = "+3\n+3\n+4\n-2\n-4\n";
+    let names = |variant_names: &[String]| -> Vec<&str> {
+        bench_day("01", input, Some(2), 3, variant_names).unwrap().iter()
+            .map(|report| report.name)
+            .collect()
+    };
+    assert_eq!(names(&[]),
+               vec!["solve_part2_naive", "solve_part2_fast", "solve_part2_fast_parallel"]);
+    assert_eq!(names(&["solve_part2_fast".to_string()]), vec!["solve_part2_fast"]);
+    let reports = bench_day("01", input, Some(2), 3, &[]).unwrap();
+    assert!(reports.iter().all(|report| report.timing.runs == 3));
+    assert!(bench_day("01", "+1\n", None, 0, &[]).is_err());
+    assert!(bench_day("01", "+1\n", None, 1, &["solve_part3".to_string()]).is_err());
+}
@@ -0,0 +1,89 @@
+// The list of days, and the one place that knows which Solver solves which day.
+
+use common::Solver;
+use common::SolverExtras;
+use day01::Day01;
+use day02::Day02;
+use day03::Day03;
+use day04::Day04;
+use day05::Day05;
+use day06::Day06;
+
+pub const DAYS: [&str; 6] = ["01", "02", "03", "04", "05", "06"];
+
+// Turns "1" or "01" into "01". Returns None for days that have no solver.
+pub fn normalize_day(day: &str) -> Option<String> {
+    let day_num: u32 = day.parse().ok()?;
+    let day = format!("{:02}", day_num);
+    if DAYS.contains(&day.as_str()) {
+        Some(day)
+    } else {
+        None
+    }
+}
+
+// Returns the path of the puzzle input checked in for the given day.
+pub fn default_input_path(day: &str) -> String {
+    format!("{}/../{}/input.txt", env!("CARGO_MANIFEST_DIR"), day)
+}
+
+// Generic code that wants to run for any day implements this, and dispatch() calls it with the
+// right Solver. This way tools like `run` and `verify` need no per-day glue.
+pub trait SolverFn {
+    type Output;
+
+    fn call<S: Solver>(self, day: &str) -> Self::Output;
+}
+
+pub fn dispatch<F: SolverFn>(day: &str, f: F) -> F::Output {
+    match day {
+        "01" => f.call::<Day01>(day),
+        "02" => f.call::<Day02>(day),
+        "03" => f.call::<Day03>(day),
+        "04" => f.call::<Day04>(day),
+        "05" => f.call::<Day05>(day),
+        "06" => f.call::<Day06>(day),
+        _ => panic!("unknown day: {}", day),
+    }
+}
+
+// Like SolverFn, for the days that implement SolverExtras.
+pub trait ExtrasFn {
+    type Output;
+
+    fn call<S: SolverExtras>(self, day: &str) -> Self::Output;
+}
+
+// Returns None for days without SolverExtras.
+pub fn dispatch_extras<F: ExtrasFn>(day: &str, f: F) -> Option<F::Output> {
+    match day {
+        "01" => Some(f.call::<Day01>(day)),
+        _ => None,
+    }
+}
+
+struct NoOp;
+
+impl ExtrasFn for NoOp {
+    type Output = ();
+
+    fn call<S: SolverExtras>(self, _day: &str) {}
+}
+
+pub fn has_extras(day: &str) -> bool {
+    dispatch_extras(day, NoOp).is_some()
+}
+
+#[test]
+fn test_normalize_day() {
+    assert_eq!(normalize_day("1"), Some("01".to_string()));
+    assert_eq!(normalize_day("06"), Some("06".to_string()));
+    assert_eq!(normalize_day("7"), None);
+    assert_eq!(normalize_day("x"), None);
+}
+
+#[test]
+fn test_has_extras() {
+    assert!(has_extras("01"));
+    assert!(!has_extras("02"));
+}
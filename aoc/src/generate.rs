@@ -0,0 +1,263 @@
+// Seeded generators of valid puzzle inputs, for stress-testing the solvers on inputs much larger
+// (or nastier) than the puzzle examples. The same seed always gives the same input.
+
+use std::collections::HashSet;
+use std::fmt::Write;
+
+use common::rng::Rng;
+
+// Length of the box IDs in the real day 02 inputs.
+pub const DEFAULT_ID_LENGTH: usize = 26;
+
+// Frequency changes, one per line with an explicit sign. The last change is picked so that the
+// drift per pass is tiny, which makes for many passes before the first repeat (or none).
+pub fn day01(rng: &mut Rng, num_changes: usize) -> String {
+    let max_change = 1 + num_changes as i64 * 10;
+    let mut changes: Vec<i64> = (1..num_changes)
+        .map(|_| rng.range(-max_change, max_change))
+        .collect();
+    if num_changes > 0 {
+        let drift = rng.range(-3, 3);
+        changes.push(drift - changes.iter().sum::<i64>());
+    }
+    let mut text = String::new();
+    for change in changes {
+        writeln!(text, "{:+}", change).unwrap();
+    }
+    text
+}
+
+// Frequency changes whose prefix sums are all distinct, so that there is no repeat within the
+// first pass, and add up to `drift`, which must not be 0. These are the inputs on which
+// solve_part2_fast has to sort its modulo groups; the bigger the drift, the more groups.
+pub fn day01_no_early_repeat(rng: &mut Rng, num_changes: usize, drift: i64) -> String {
+    assert!(drift != 0, "a zero drift always repeats within the first pass");
+    let span = 10 * num_changes as i64 + drift.abs();
+    let mut seen: HashSet<i64> = HashSet::new();
+    seen.insert(0);
+    seen.insert(drift);
+    let mut sums: Vec<i64> = Vec::new();
+    while sums.len() + 1 < num_changes {
+        let sum = rng.range(-span, span);
+        if seen.insert(sum) {
+            sums.push(sum);
+        }
+    }
+    if num_changes > 0 {
+        sums.push(drift);
+    }
+    let mut text = String::new();
+    let mut previous = 0;
+    for sum in sums {
+        writeln!(text, "{:+}", sum - previous).unwrap();
+        previous = sum;
+    }
+    text
+}
+
+fn random_id(rng: &mut Rng, id_length: usize) -> Vec<u8> {
+    (0..id_length).map(|_| b'a' + rng.below(26) as u8).collect()
+}
+
+// Box IDs of a fixed length. Exactly one pair differs in a single position (barring a
+// vanishingly unlikely accident for long IDs); the rest are random.
+pub fn day02(rng: &mut Rng, num_ids: usize, id_length: usize) -> String {
+    assert!(id_length > 0);
+    let mut ids: Vec<Vec<u8>> = (0..num_ids).map(|_| random_id(rng, id_length)).collect();
+    if num_ids >= 2 {
+        let mut near_duplicate = ids[0].clone();
+        let index = rng.below(id_length as u64) as usize;
+        let offset = 1 + rng.below(25) as u8;
+        near_duplicate[index] = b'a' + (near_duplicate[index] - b'a' + offset) % 26;
+        ids[1] = near_duplicate;
+        rng.shuffle(&mut ids);
+    }
+    let mut text = String::new();
+    for id in ids {
+        text.push_str(&String::from_utf8(id).unwrap());
+        text.push('\n');
+    }
+    text
+}
+
+// Claims on the usual 1000x1000 fabric.
+pub fn day03(rng: &mut Rng, num_claims: usize) -> String {
+    let mut text = String::new();
+    for id in 1..=num_claims {
+        let num_cols = rng.range(1, 30);
+        let num_rows = rng.range(1, 30);
+        let start_col = rng.range(0, 1000 - num_cols);
+        let start_row = rng.range(0, 1000 - num_rows);
+        writeln!(text, "#{} @ {},{}: {}x{}", id, start_col, start_row, num_cols, num_rows).unwrap();
+    }
+    text
+}
+
+const DAYS_IN_MONTH: [u32; 12] = [31, 28, 31, 30, 31, 30, 31, 31, 30, 31, 30, 31];
+
+// Returns the date that is `offset` days after 1518-01-01, as "YYYY-MM-DD". Every year has 365
+// days, which is all the solvers need: they only sort the dates.
+fn date(offset: usize) -> String {
+    let year = 1518 + offset / 365;
+    let mut day_of_year = (offset % 365) as u32;
+    for (month, &days) in DAYS_IN_MONTH.iter().enumerate() {
+        if day_of_year < days {
+            return format!("{}-{:02}-{:02}", year, month + 1, day_of_year + 1);
+        }
+        day_of_year -= days;
+    }
+    unreachable!()
+}
+
+// Guard logs for `num_shifts` consecutive nights, shuffled. Each shift begins shortly before or
+// after midnight, and has one to three naps during the midnight hour, so every falls-asleep is
+// followed by a wakes-up before the next shift. After 365 nights, the dates go on into 1519 and
+// later.
+pub fn day04(rng: &mut Rng, num_shifts: usize) -> String {
+    let num_guards = 1 + num_shifts / 10;
+    let mut guard_ids: HashSet<i64> = HashSet::new();
+    while guard_ids.len() < num_guards {
+        guard_ids.insert(rng.range(1, 3500));
+    }
+    let mut guard_ids: Vec<i64> = guard_ids.into_iter().collect();
+    guard_ids.sort_unstable();
+
+    let mut lines: Vec<String> = Vec::new();
+    for shift in 0..num_shifts {
+        let date_of_shift = date(shift + 1);
+        let guard_id = rng.choose(&guard_ids);
+        let first_nap_minute = if rng.chance(1, 2) {
+            lines.push(format!("[{} 23:{:02}] Guard #{} begins shift",
+                               date(shift), rng.range(45, 59), guard_id));
+            0
+        } else {
+            let minute = rng.range(0, 5);
+            lines.push(format!("[{} 00:{:02}] Guard #{} begins shift",
+                               date_of_shift, minute, guard_id));
+            minute + 1
+        };
+        let num_naps = rng.range(1, 3) as usize;
+        let mut minutes: HashSet<i64> = HashSet::new();
+        while minutes.len() < 2 * num_naps {
+            minutes.insert(rng.range(first_nap_minute, 59));
+        }
+        let mut minutes: Vec<i64> = minutes.into_iter().collect();
+        minutes.sort_unstable();
+        for nap in minutes.chunks(2) {
+            lines.push(format!("[{} 00:{:02}] falls asleep", date_of_shift, nap[0]));
+            lines.push(format!("[{} 00:{:02}] wakes up", date_of_shift, nap[1]));
+        }
+    }
+    rng.shuffle(&mut lines);
+    let mut text = lines.join("\n");
+    text.push('\n');
+    text
+}
+
+// A polymer of `num_units` units. A third of the units are the opposite polarity of the unit
+// before, so that there is plenty to react.
+pub fn day05(rng: &mut Rng, num_units: usize) -> String {
+    let mut polymer = String::new();
+    let mut prev: Option<char> = None;
+    for _ in 0..num_units {
+        let c = match prev {
+            Some(p) if rng.chance(1, 3) => {
+                if p.is_lowercase() { p.to_ascii_uppercase() } else { p.to_ascii_lowercase() }
+            },
+            _ => {
+                let c = (b'a' + rng.below(26) as u8) as char;
+                if rng.chance(1, 2) { c.to_ascii_uppercase() } else { c }
+            },
+        };
+        polymer.push(c);
+        prev = Some(c);
+    }
+    polymer.push('\n');
+    polymer
+}
+
+// Distinct coordinates in a square big enough to hold them.
+pub fn day06(rng: &mut Rng, num_coords: usize) -> String {
+    let max = 40.max((num_coords as f64).sqrt() as i64 * 8);
+    let mut seen: HashSet<(i64, i64)> = HashSet::new();
+    let mut text = String::new();
+    while seen.len() < num_coords {
+        let coord = (rng.range(0, max), rng.range(0, max));
+        if seen.insert(coord) {
+            writeln!(text, "{}, {}", coord.0, coord.1).unwrap();
+        }
+    }
+    text
+}
+
+// Generates an input for the given day. `size` is the number of records (lines, or units for
+// day 05). `id_length` is only used by day 02.
+pub fn generate(day: &str, rng: &mut Rng, size: usize, id_length: usize) -> String {
+    match day {
+        "01" => day01(rng, size),
+        "02" => day02(rng, size, id_length),
+        "03" => day03(rng, size),
+        "04" => day04(rng, size),
+        "05" => day05(rng, size),
+        "06" => day06(rng, size),
+        _ => panic!("unknown day: {}", day),
+    }
+}
+
+#[test]
+fn test_same_seed_same_input() {
+    for day in ::days::DAYS.iter() {
+        let a = generate(day, &mut Rng::new(5), 50, DEFAULT_ID_LENGTH);
+        let b = generate(day, &mut Rng::new(5), 50, DEFAULT_ID_LENGTH);
+        let c = generate(day, &mut Rng::new(6), 50, DEFAULT_ID_LENGTH);
+        assert_eq!(a, b);
+        assert!(a != c);
+    }
+}
+
+#[test]
+fn test_inputs_are_valid() {
+    use answers::Answers;
+    use verify;
+
+    // verify_day() parses and solves every part; an empty manifest just reports them missing.
+    let answers = Answers::parse("").unwrap();
+    for seed in 0..5 {
+        for day in ::days::DAYS.iter() {
+            let input = generate(day, &mut Rng::new(seed), 100, DEFAULT_ID_LENGTH);
+            verify::verify_day(day, &input, &answers).unwrap();
+        }
+    }
+}
+
+#[test]
+fn test_day02_near_duplicate() {
+    let input = day02(&mut Rng::new(3), 1000, 64);
+    let ids: Vec<&str> = input.lines().collect();
+    assert_eq!(ids.len(), 1000);
+    assert!(ids.iter().all(|id| id.len() == 64));
+    assert_eq!(::day02::solve_part2(&ids).map(|found| found.common().len()), Some(63));
+}
+
+#[test]
+fn test_day04_more_than_a_year() {
+    let input = day04(&mut Rng::new(4), 400);
+    assert!(input.contains("[1519-01-01 00:"));
+    let log_lines = ::day04::parse_input(&input).unwrap();
+    assert_eq!(log_lines.iter().filter(|l| l.action == ::day04::Action::BeginsShift).count(), 400);
+    assert!(::day04::solve_part1(&log_lines).is_some());
+}
+
+#[test]
+fn test_day01_no_early_repeat() {
+    let text = day01_no_early_repeat(&mut Rng::new(5), 1000, -37);
+    let mut seen: HashSet<i64> = HashSet::new();
+    seen.insert(0);
+    let mut sum = 0;
+    for line in text.lines() {
+        sum += line.parse::<i64>().unwrap();
+        assert!(seen.insert(sum), "{} repeats", sum);
+    }
+    assert_eq!(seen.len(), 1001);
+    assert_eq!(sum, -37);
+}
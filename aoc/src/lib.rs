@@ -0,0 +1,16 @@
+// Tools that work over every day: running solvers, checking their answers, timing them, and
+// generating inputs for them.
+
+extern crate common;
+extern crate day01;
+extern crate day02;
+extern crate day03;
+extern crate day04;
+extern crate day05;
+extern crate day06;
+
+pub mod answers;
+pub mod bench;
+pub mod days;
+pub mod generate;
+pub mod verify;
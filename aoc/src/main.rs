@@ -0,0 +1,545 @@
+// Runs the solvers for one or all days, or checks their answers.
+//
+// Usage:
+//     aoc run <day|all> [--part 1|2] [--input PATH] [--format text|json] [--explain]
+//             [--naive [--max-passes N] [--max-ms N]] [--stream]
+//     aoc stats <day> [--input PATH] [--format text|json]
+//     aoc verify [--answers PATH]
+//     aoc bench <day|all> [--part 1|2] [--input PATH] [--runs N] [--variant NAME]...
+//     aoc generate <day> [--seed N] [--size N] [--length N] [--drift N]
+//
+// By default, the input for day NN is read from NN/input.txt. Use `--input -` to read it from
+// stdin instead. Blank lines are skipped, with a warning on stderr. With `--format json`, each
+// part's answer is printed as one JSON object per line, e.g.
+// {"day":"05","part":2,"answer":{"char_removed":"v","length":4944}}. With `--explain`, the answers
+// are followed by a step-by-step derivation of part 2 (day 01 only, text format only).
+// With `--naive`, part 2 is solved by the slow but obviously correct solver (day 01 only), within
+// the budget of passes and/or milliseconds given by `--max-passes` and
+// `--max-ms`. If the budget runs out, it says so on stderr and falls back to the fast solver.
+// With `--stream`, the input is read a line at a time instead of all at once, so that inputs too
+// big for memory can be solved from a pipe (day 01 only). The first bad token stops the run.
+//
+// `stats` prints numbers that describe the input (day 01 only), e.g. the range of frequencies on
+// the first pass, and how many passes a repeat could take.
+//
+// `verify` runs every day on its NN/input.txt and compares the answers with answers.toml.
+//
+// `bench` times every registered variant of each part (see Solver::variants) and prints the min,
+// median and max wall time. Build with --release for meaningful numbers.
+//
+// `generate` prints a random valid input with `--size` records; the same seed always gives the
+// same input. `--length` sets the length of the box IDs for day 02. `--drift` makes a day 01 input
+// with that total and no repeat within the first pass, which is the slow case for part 2. For
+// example:
+//     aoc generate 1 --size 100000 | aoc bench 1 --input -
+
+extern crate aoc;
+extern crate common;
+
+use aoc::answers;
+use aoc::bench;
+use aoc::answers::Answers;
+use aoc::days;
+use aoc::days::DAYS;
+use aoc::days::ExtrasFn;
+use aoc::days::SolverFn;
+use aoc::generate;
+use aoc::verify;
+use aoc::verify::Status;
+use common::Budget;
+use common::Json;
+use common::Solver;
+use common::SolverExtras;
+use common::ToJson;
+use common::input::Input;
+use common::rng::Rng;
+use std::env;
+use std::fmt::Debug;
+use std::fs::File;
+use std::io;
+use std::io::BufRead;
+use std::io::BufReader;
+use std::process;
+use std::time::Duration;
+
+const USAGE: &str = "usage:
+    aoc run <day|all> [--part 1|2] [--input PATH] [--format text|json] [--explain]
+            [--naive [--max-passes N] [--max-ms N]] [--stream]
+    aoc stats <day> [--input PATH] [--format text|json]
+    aoc verify [--answers PATH]
+    aoc bench <day|all> [--part 1|2] [--input PATH] [--runs N] [--variant NAME]...
+    aoc generate <day> [--seed N] [--size N] [--length N] [--drift N]";
+
+#[derive(Clone, Copy, PartialEq)]
+enum Format {
+    Text,
+    Json,
+}
+
+struct RunArgs {
+    days: Vec<String>,
+    part: Option<u32>,
+    input_path: Option<String>,
+    format: Format,
+    explain: bool,
+    // The budget for the naive part 2 solver, or None to use the fast one.
+    naive: Option<Budget>,
+    stream: bool,
+    runs: usize,
+    // The variants to bench, or empty for all of them.
+    variants: Vec<String>,
+}
+
+// Parses the arguments shared by `run`, `stats` and `bench`. Only `run` takes --explain, --stream
+// and the --naive flags, only `run` and `stats` take --format, only `bench` takes --runs and
+// --variant, and `stats` takes no --part.
+fn parse_run_args(command: &str, args: &[String]) -> Result<RunArgs, String> {
+    let mut days = None;
+    let mut part = None;
+    let mut input_path = None;
+    let mut format = Format::Text;
+    let mut explain = false;
+    let mut naive = false;
+    let mut stream = false;
+    let mut budget = Budget::unlimited();
+    let mut runs = 10;
+    let mut variants: Vec<String> = Vec::new();
+    let mut args = args.iter();
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--part" if command != "stats" => {
+                let value = args.next().ok_or("--part needs a value")?;
+                match value.as_str() {
+                    "1" => part = Some(1),
+                    "2" => part = Some(2),
+                    _ => return Err(format!("invalid part: {}", value)),
+                }
+            },
+            "--input" => {
+                let value = args.next().ok_or("--input needs a value")?;
+                input_path = Some(value.clone());
+            },
+            "--format" if command == "run" || command == "stats" => {
+                let value = args.next().ok_or("--format needs a value")?;
+                match value.as_str() {
+                    "text" => format = Format::Text,
+                    "json" => format = Format::Json,
+                    _ => return Err(format!("invalid format: {}", value)),
+                }
+            },
+            "--explain" if command == "run" => explain = true,
+            "--naive" if command == "run" => naive = true,
+            "--stream" if command == "run" => stream = true,
+            "--max-passes" | "--max-ms" if command == "run" => {
+                let value = args.next().ok_or(format!("{} needs a value", arg))?;
+                let number: u64 = value.parse()
+                    .map_err(|_| format!("invalid value for {}: {}", arg, value))?;
+                if arg == "--max-passes" {
+                    budget.max_passes = Some(number);
+                } else {
+                    budget.max_time = Some(Duration::from_millis(number));
+                }
+            },
+            "--runs" if command == "bench" => {
+                let value = args.next().ok_or("--runs needs a value")?;
+                runs = value.parse().map_err(|_| format!("invalid number of runs: {}", value))?;
+            },
+            "--variant" if command == "bench" => {
+                let value = args.next().ok_or("--variant needs a value")?;
+                variants.push(value.clone());
+            },
+            "all" if days.is_none() => {
+                days = Some(DAYS.iter().map(|d| d.to_string()).collect());
+            },
+            _ if days.is_none() => {
+                let day = days::normalize_day(arg).ok_or(format!("unknown day: {}", arg))?;
+                days = Some(vec![day]);
+            },
+            _ => return Err(format!("unexpected argument: {}", arg)),
+        }
+    }
+    let days: Vec<String> = days.ok_or("missing day")?;
+    if input_path.is_some() && days.len() > 1 {
+        return Err("--input cannot be used with all".to_string());
+    }
+    if explain && format != Format::Text {
+        return Err("--explain can only be used with --format text".to_string());
+    }
+    if !naive && budget != Budget::unlimited() {
+        return Err("--max-passes and --max-ms can only be used with --naive".to_string());
+    }
+    if stream && (explain || naive || days.len() > 1) {
+        return Err("--stream cannot be used with --explain, --naive or all".to_string());
+    }
+    // What needs SolverExtras fails here for the days without it, before any input is read.
+    let extra = if command == "stats" {
+        Some("stats")
+    } else if explain {
+        Some("--explain")
+    } else if naive {
+        Some("--naive")
+    } else if stream {
+        Some("--stream")
+    } else {
+        None
+    };
+    if let Some(extra) = extra {
+        if let Some(day) = days.iter().find(|day| !days::has_extras(day)) {
+            return Err(format!("{} is not supported for day {}", extra, day));
+        }
+    }
+    let naive = if naive { Some(budget) } else { None };
+    Ok(RunArgs{days, part, input_path, format, explain, naive, stream, runs, variants})
+}
+
+// Reads the input for the given day, either from the given path or from NN/input.txt. The parsers
+// skip blank lines, so they are reported on stderr, in case they mean the input is cut short.
+fn read_input(day: &str, input_path: &Option<String>) -> Result<Input, String> {
+    let input = match input_path {
+        Some(ref path) if path == "-" => {
+            Input::from_stdin().map_err(|e| format!("could not read stdin: {}", e))
+        },
+        Some(ref path) => {
+            Input::from_path(path).map_err(|e| format!("could not read {}: {}", path, e))
+        },
+        None => {
+            let path = days::default_input_path(day);
+            Input::from_path(&path).map_err(|e| format!("could not read {}: {}", path, e))
+        },
+    }?;
+    let blank_lines = input.lines().blank_line_numbers();
+    if !blank_lines.is_empty() {
+        eprintln!("warning: day {}: skipping {}", day, describe_lines(&blank_lines));
+    }
+    Ok(input)
+}
+
+// E.g. "blank lines 2, 5, 9 and 3 more".
+fn describe_lines(numbers: &[usize]) -> String {
+    const MAX_LISTED: usize = 3;
+    let listed: Vec<String> = numbers.iter().take(MAX_LISTED).map(|n| n.to_string()).collect();
+    let mut text = format!("blank line{} {}", if numbers.len() == 1 { "" } else { "s" },
+                           listed.join(", "));
+    if numbers.len() > MAX_LISTED {
+        text += &format!(" and {} more", numbers.len() - MAX_LISTED);
+    }
+    text
+}
+
+fn wants(part: Option<u32>, p: u32) -> bool {
+    part.is_none() || part == Some(p)
+}
+
+fn print_answer<T: Debug + ToJson>(day: &str, part: u32, answer: &T, format: Format) {
+    match format {
+        Format::Text => println!("part {}: {:?}", part, answer),
+        Format::Json => {
+            let object = Json::object(vec![
+                ("day", day.to_string().to_json()),
+                ("part", part.to_json()),
+                ("answer", answer.to_json())]);
+            println!("{}", object);
+        },
+    }
+}
+
+// A day's SolverExtras::explain.
+type Explain<S> = fn(&<S as Solver>::Input) -> Result<String, String>;
+// A way to solve a day's part 2, e.g. Solver::part2.
+type SolvePart2<'a, S> =
+    dyn Fn(&<S as Solver>::Input) -> Result<<S as Solver>::Output2, String> + 'a;
+
+struct RunSolver<'a> {
+    input: &'a str,
+    part: Option<u32>,
+    format: Format,
+    explain: bool,
+    naive: Option<Budget>,
+}
+
+impl<'a> SolverFn for RunSolver<'a> {
+    type Output = Result<(), String>;
+
+    fn call<S: Solver>(self, day: &str) -> Result<(), String> {
+        run_solver::<S>(day, self.input, self.part, self.format, &S::part2, None)
+    }
+}
+
+// For --explain and --naive, which need SolverExtras.
+impl<'a> ExtrasFn for RunSolver<'a> {
+    type Output = Result<(), String>;
+
+    fn call<S: SolverExtras>(self, day: &str) -> Result<(), String> {
+        let naive = self.naive;
+        let solve_part2 = |parsed: &S::Input| match naive {
+            Some(budget) => solve_part2_naive::<S>(parsed, &budget),
+            None => S::part2(parsed),
+        };
+        let explain = if self.explain { Some(S::explain as Explain<S>) } else { None };
+        run_solver::<S>(day, self.input, self.part, self.format, &solve_part2, explain)
+    }
+}
+
+// Solves part 2 with the naive solver within the given budget. Falls back to the fast one if the
+// budget runs out.
+fn solve_part2_naive<S: SolverExtras>(parsed: &S::Input, budget: &Budget)
+        -> Result<S::Output2, String> {
+    match S::part2_reference(parsed, budget)? {
+        Some(answer) => Ok(answer),
+        None => {
+            eprintln!("part 2: the naive solver ran out of budget; falling back to the fast one");
+            S::part2(parsed)
+        },
+    }
+}
+
+// Prints every parse error before giving up, so that a bad input can be fixed in one go.
+fn run_solver<S: Solver>(day: &str, input: &str, part: Option<u32>, format: Format,
+                         solve_part2: &SolvePart2<S>,
+                         explain: Option<Explain<S>>) -> Result<(), String> {
+    let parsed = S::parse(input).map_err(|errors| {
+        for error in &errors {
+            eprintln!("{}", error);
+        }
+        format!("{} parse errors", errors.len())
+    })?;
+    if wants(part, 1) {
+        let answer = S::part1(&parsed).map_err(|message| format!("part 1: {}", message))?;
+        print_answer(day, 1, &answer, format);
+    }
+    if wants(part, 2) {
+        if S::HAS_PART2 {
+            let answer = solve_part2(&parsed)
+                .map_err(|message| format!("part 2: {}", message))?;
+            print_answer(day, 2, &answer, format);
+        } else if format == Format::Text {
+            println!("part 2: not implemented");
+        }
+    }
+    if let Some(explain) = explain {
+        print!("{}", explain(&parsed)?);
+    }
+    Ok(())
+}
+
+struct StreamSolver<'a> {
+    reader: &'a mut dyn BufRead,
+    part: Option<u32>,
+    format: Format,
+}
+
+impl<'a> ExtrasFn for StreamSolver<'a> {
+    type Output = Result<(), String>;
+
+    fn call<S: SolverExtras>(self, day: &str) -> Result<(), String> {
+        let (answer1, answer2) = S::solve_stream(self.reader)?;
+        if wants(self.part, 1) {
+            print_answer(day, 1, &answer1, self.format);
+        }
+        if wants(self.part, 2) && S::HAS_PART2 {
+            print_answer(day, 2, &answer2, self.format);
+        }
+        Ok(())
+    }
+}
+
+// Like run(), but reads the input for one day a line at a time.
+fn run_streaming(run_args: &RunArgs) -> Result<(), String> {
+    let day = &run_args.days[0];
+    let path = run_args.input_path.clone().unwrap_or_else(|| days::default_input_path(day));
+    let stdin = io::stdin();
+    let mut reader: Box<dyn BufRead> = if path == "-" {
+        Box::new(stdin.lock())
+    } else {
+        let file = File::open(&path).map_err(|e| format!("could not read {}: {}", path, e))?;
+        Box::new(BufReader::new(file))
+    };
+    days::dispatch_extras(day, StreamSolver{reader: &mut *reader, part: run_args.part,
+                                            format: run_args.format})
+        .unwrap_or_else(|| Err("--stream is not supported".to_string()))
+        .map_err(|message| format!("day {}: {}", day, message))
+}
+
+// Keeps going after a day fails, so that one bad input does not hide the other days' results.
+fn run(args: &[String]) -> Result<(), String> {
+    let run_args = parse_run_args("run", args)?;
+    if run_args.stream {
+        return run_streaming(&run_args);
+    }
+    let mut failed_days: Vec<&str> = Vec::new();
+    for day in &run_args.days {
+        if run_args.days.len() > 1 && run_args.format == Format::Text {
+            println!("day {}", day);
+        }
+        let result = read_input(day, &run_args.input_path).and_then(|input| {
+            let run_solver = RunSolver{
+                input: input.text(),
+                part: run_args.part,
+                format: run_args.format,
+                explain: run_args.explain,
+                naive: run_args.naive,
+            };
+            if run_args.explain || run_args.naive.is_some() {
+                days::dispatch_extras(day, run_solver)
+                    .unwrap_or_else(|| Err("--explain and --naive are not supported".to_string()))
+            } else {
+                days::dispatch(day, run_solver)
+            }
+        });
+        if let Err(message) = result {
+            eprintln!("error: day {}: {}", day, message);
+            failed_days.push(day);
+        }
+    }
+    if failed_days.is_empty() {
+        Ok(())
+    } else {
+        Err(format!("failed days: {}", failed_days.join(", ")))
+    }
+}
+
+struct StatsSolver<'a> {
+    input: &'a str,
+    format: Format,
+}
+
+impl<'a> ExtrasFn for StatsSolver<'a> {
+    type Output = Result<(), String>;
+
+    fn call<S: SolverExtras>(self, day: &str) -> Result<(), String> {
+        let parsed = S::parse(self.input)
+            .map_err(|errors| format!("{} parse errors", errors.len()))?;
+        let stats = S::stats(&parsed)?;
+        match (self.format, &stats) {
+            (Format::Text, Json::Object(fields)) => {
+                for (name, value) in fields {
+                    println!("{}: {}", name, value);
+                }
+            },
+            _ => {
+                println!("{}", Json::object(vec![("day", day.to_string().to_json()),
+                                                 ("stats", stats)]));
+            },
+        }
+        Ok(())
+    }
+}
+
+fn stats(args: &[String]) -> Result<(), String> {
+    let run_args = parse_run_args("stats", args)?;
+    if run_args.days.len() > 1 {
+        return Err("stats cannot be used with all".to_string());
+    }
+    let day = &run_args.days[0];
+    let input = read_input(day, &run_args.input_path)?;
+    days::dispatch_extras(day, StatsSolver{input: input.text(), format: run_args.format})
+        .unwrap_or_else(|| Err("stats is not supported".to_string()))
+        .map_err(|message| format!("day {}: {}", day, message))
+}
+
+fn bench(args: &[String]) -> Result<(), String> {
+    let run_args = parse_run_args("bench", args)?;
+    if cfg!(debug_assertions) {
+        eprintln!("warning: this is a debug build; use --release for meaningful numbers");
+    }
+    for day in &run_args.days {
+        let input = read_input(day, &run_args.input_path)?;
+        let reports = bench::bench_day(day, input.text(), run_args.part, run_args.runs,
+                                       &run_args.variants)
+            .map_err(|message| format!("day {}: {}", day, message))?;
+        for report in reports {
+            println!("{}", report);
+        }
+    }
+    Ok(())
+}
+
+fn generate(args: &[String]) -> Result<(), String> {
+    let mut day = None;
+    let mut seed: u64 = 0;
+    let mut size: usize = 1000;
+    let mut id_length = generate::DEFAULT_ID_LENGTH;
+    let mut drift: Option<i64> = None;
+    let mut args = args.iter();
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--seed" | "--size" | "--length" => {
+                let value = args.next().ok_or(format!("{} needs a value", arg))?;
+                let number: u64 = value.parse()
+                    .map_err(|_| format!("invalid value for {}: {}", arg, value))?;
+                match arg.as_str() {
+                    "--seed" => seed = number,
+                    "--size" => size = number as usize,
+                    _ => id_length = number as usize,
+                }
+            },
+            "--drift" => {
+                let value = args.next().ok_or("--drift needs a value")?;
+                drift = Some(value.parse().map_err(|_| format!("invalid drift: {}", value))?);
+            },
+            _ if day.is_none() => {
+                day = Some(days::normalize_day(arg).ok_or(format!("unknown day: {}", arg))?);
+            },
+            _ => return Err(format!("unexpected argument: {}", arg)),
+        }
+    }
+    let day = day.ok_or("missing day")?;
+    if id_length == 0 {
+        return Err("--length must be positive".to_string());
+    }
+    let mut rng = Rng::new(seed);
+    match drift {
+        Some(_) if day != "01" => return Err("--drift only applies to day 01".to_string()),
+        Some(0) => return Err("--drift must not be 0".to_string()),
+        Some(drift) => print!("{}", generate::day01_no_early_repeat(&mut rng, size, drift)),
+        None => print!("{}", generate::generate(&day, &mut rng, size, id_length)),
+    }
+    Ok(())
+}
+
+fn verify(args: &[String]) -> Result<(), String> {
+    let answers_path = match args {
+        [] => answers::default_path(),
+        [flag, path] if flag == "--answers" => path.clone(),
+        _ => return Err(USAGE.to_string()),
+    };
+    let answers = Answers::from_path(&answers_path)?;
+    let mut num_failed = 0;
+    for (day, result) in verify::verify_all(&answers) {
+        match result {
+            Ok(reports) => {
+                for report in reports {
+                    println!("{}", report);
+                    if let Status::Fail{..} = report.status {
+                        num_failed += 1;
+                    }
+                }
+            },
+            Err(message) => {
+                println!("day {}: ERROR: {}", day, message);
+                num_failed += 1;
+            },
+        }
+    }
+    if num_failed == 0 {
+        Ok(())
+    } else {
+        Err(format!("{} failures", num_failed))
+    }
+}
+
+fn main() {
+    let args: Vec<String> = env::args().skip(1).collect();
+    let result = match args.first().map(|s| s.as_str()) {
+        Some("run") => run(&args[1..]),
+        Some("stats") => stats(&args[1..]),
+        Some("verify") => verify(&args[1..]),
+        Some("bench") => bench(&args[1..]),
+        Some("generate") => generate(&args[1..]),
+        _ => Err(USAGE.to_string()),
+    };
+    if let Err(message) = result {
+        eprintln!("error: {}", message);
+        process::exit(1);
+    }
+}
@@ -0,0 +1,115 @@
+// Checks every day's answers on its checked-in input against the answers manifest, so that
+// refactors that change an answer are caught immediately.
+
+use std::fmt;
+
+use common::Solver;
+use common::ToJson;
+use common::input::Input;
+
+use answers::Answers;
+use days;
+use days::SolverFn;
+
+#[derive(Debug, PartialEq)]
+pub enum Status {
+    Pass,
+    Fail { expected: String },
+    // There is no recorded answer for this part yet.
+    Missing,
+}
+
+#[derive(Debug, PartialEq)]
+pub struct PartReport {
+    pub day: String,
+    pub part: u32,
+    pub actual: String,
+    pub status: Status,
+}
+
+impl fmt::Display for PartReport {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "day {} part {}: ", self.day, self.part)?;
+        match self.status {
+            Status::Pass => write!(f, "pass"),
+            Status::Fail{ref expected} => {
+                write!(f, "FAIL: expected {}, got {}", expected, self.actual)
+            },
+            Status::Missing => write!(f, "missing (got {})", self.actual),
+        }
+    }
+}
+
+// Solves every implemented part, and returns the answers as JSON text.
+struct SolveToJson<'a> {
+    input: &'a str,
+}
+
+impl<'a> SolverFn for SolveToJson<'a> {
+    type Output = Result<Vec<(u32, String)>, String>;
+
+    fn call<S: Solver>(self, _day: &str) -> Result<Vec<(u32, String)>, String> {
+        let parsed = S::parse(self.input).map_err(|errors| {
+            let messages: Vec<String> = errors.iter().map(|e| e.to_string()).collect();
+            messages.join("\n")
+        })?;
+        let part1 = S::part1(&parsed).map_err(|message| format!("part 1: {}", message))?;
+        let mut answers = vec![(1, part1.to_json().to_string())];
+        if S::HAS_PART2 {
+            let part2 = S::part2(&parsed).map_err(|message| format!("part 2: {}", message))?;
+            answers.push((2, part2.to_json().to_string()));
+        }
+        Ok(answers)
+    }
+}
+
+// Runs the given day on the given input, and compares each part with the manifest.
+pub fn verify_day(day: &str, input: &str, answers: &Answers)
+        -> Result<Vec<PartReport>, String> {
+    let actual_answers = days::dispatch(day, SolveToJson{input})?;
+    Ok(actual_answers.into_iter().map(|(part, actual)| {
+        let status = match answers.get(day, part) {
+            None => Status::Missing,
+            Some(expected) if expected == actual => Status::Pass,
+            Some(expected) => Status::Fail{expected: expected.to_string()},
+        };
+        PartReport{day: day.to_string(), part, actual, status}
+    }).collect())
+}
+
+// Verifies every day on its checked-in input. Errors (unreadable or unparseable inputs) are
+// reported per day.
+pub fn verify_all(answers: &Answers) -> Vec<(String, Result<Vec<PartReport>, String>)> {
+    days::DAYS.iter().map(|day| {
+        let path = days::default_input_path(day);
+        let result = Input::from_path(&path)
+            .map_err(|e| format!("could not read {}: {}", path, e))
+            .and_then(|input| verify_day(day, input.text(), answers));
+        (day.to_string(), result)
+    }).collect()
+}
+
+#[test]
+fn test_verify_day() {
+    let answers = Answers::parse(r#"
+        [[answer]]
+        day = "01"
+        part = 1
+        expected = '3'
+
+        [[answer]]
+        day = "02"
+        part = 1
+        expected = '{"num_2":1,"num_3":1,"checksum":2}'
+    "#).unwrap();
+    let reports = verify_day("01", "+1\n+2\n", &answers).unwrap();
+    assert_eq!(reports, vec![
+        PartReport{day: "01".to_string(), part: 1, actual: "3".to_string(), status: Status::Pass},
+        PartReport{day: "01".to_string(), part: 2, actual: "null".to_string(),
+                   status: Status::Missing},
+    ]);
+    let reports = verify_day("02", "abcdef\nbababc\n", &answers).unwrap();
+    assert_eq!(reports[0].status, Status::Fail{
+        expected: "{\"num_2\":1,\"num_3\":1,\"checksum\":2}".to_string()});
+    assert!(verify_day("01", "+1\nfoo\n", &answers).is_err());
+}
@@ -0,0 +1,19 @@
+// Runs every day on its checked-in input, and checks the answers against answers.toml.
+
+extern crate aoc;
+
+use aoc::answers;
+use aoc::answers::Answers;
+use aoc::verify;
+use aoc::verify::Status;
+
+#[test]
+fn test_all_answers() {
+    let answers = Answers::from_path(&answers::default_path()).unwrap();
+    for (day, result) in verify::verify_all(&answers) {
+        let reports = result.unwrap_or_else(|message| panic!("day {}: {}", day, message));
+        for report in reports {
+            assert_eq!(report.status, Status::Pass, "{}", report);
+        }
+    }
+}
@@ -0,0 +1,44 @@
+// A limit on how long a solver may run, for solvers that loop over the input until they are done
+// (e.g. day 01's solve_part2_naive), so that a hard input gives up instead of spinning.
+
+use std::time::Duration;
+use std::time::Instant;
+
+// Interpretation: stop before starting pass number max_passes + 1, or once max_time has passed.
+// None means no limit.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct Budget {
+    pub max_passes: Option<u64>,
+    pub max_time: Option<Duration>,
+}
+
+impl Budget {
+    pub fn unlimited() -> Budget {
+        Budget::default()
+    }
+
+    pub fn passes(max_passes: u64) -> Budget {
+        Budget{max_passes: Some(max_passes), max_time: None}
+    }
+
+    pub fn time(max_time: Duration) -> Budget {
+        Budget{max_passes: None, max_time: Some(max_time)}
+    }
+
+    // Returns true iff, having done `passes` passes since `start`, there is no budget left for
+    // another.
+    pub fn exceeded(&self, passes: u64, start: Instant) -> bool {
+        self.max_passes.is_some_and(|max_passes| passes >= max_passes)
+            || self.max_time.is_some_and(|max_time| start.elapsed() >= max_time)
+    }
+}
+
+#[test]
+fn test_exceeded() {
+    let start = Instant::now();
+    assert!(!Budget::unlimited().exceeded(u64::MAX, start));
+    assert!(!Budget::passes(3).exceeded(2, start));
+    assert!(Budget::passes(3).exceeded(3, start));
+    assert!(Budget::time(Duration::ZERO).exceeded(0, start));
+    assert!(!Budget::time(Duration::from_secs(3600)).exceeded(1000, start));
+}
@@ -0,0 +1,123 @@
+// Reading puzzle inputs. Inputs may come from stdin, a file, or a string, and may have been saved
+// on Windows, so we strip a leading UTF-8 BOM and "\r\n" line endings before anyone sees them.
+
+use std::fs;
+use std::io;
+use std::io::Read;
+use std::path::Path;
+use std::str;
+
+const BOM: char = '\u{feff}';
+
+// A whole puzzle input, already cleaned up.
+pub struct Input {
+    text: String,
+}
+
+impl Input {
+    pub fn from_stdin() -> io::Result<Input> {
+        let mut text = String::new();
+        io::stdin().read_to_string(&mut text)?;
+        Ok(Input::from_text(&text))
+    }
+
+    pub fn from_path<P: AsRef<Path>>(path: P) -> io::Result<Input> {
+        Ok(Input::from_text(&fs::read_to_string(path)?))
+    }
+
+    pub fn from_text(text: &str) -> Input {
+        Input{text: clean(text)}
+    }
+
+    // The whole input, without the BOM and with "\n" line endings.
+    pub fn text(&self) -> &str {
+        &self.text
+    }
+
+    pub fn lines(&self) -> Lines<'_> {
+        lines(&self.text)
+    }
+}
+
+// Strips the BOM and turns "\r\n" into "\n".
+pub fn clean(text: &str) -> String {
+    text.trim_start_matches(BOM).replace("\r\n", "\n")
+}
+
+// A single line of input. The number is 1-based, for error messages.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Line<'a> {
+    pub number: usize,
+    pub text: &'a str,
+}
+
+impl<'a> Line<'a> {
+    pub fn is_blank(&self) -> bool {
+        self.text.trim().is_empty()
+    }
+}
+
+// Iterates over the lines of an input, stripping the BOM and "\r" line endings on the fly.
+pub struct Lines<'a> {
+    inner: str::Lines<'a>,
+    number: usize,
+    skip_blank: bool,
+}
+
+impl<'a> Lines<'a> {
+    // Skips lines that are empty or contain only whitespace.
+    pub fn skip_blank(mut self) -> Lines<'a> {
+        self.skip_blank = true;
+        self
+    }
+
+    // Returns the numbers of the blank lines, so callers can report them.
+    pub fn blank_line_numbers(self) -> Vec<usize> {
+        self.filter(|line| line.is_blank()).map(|line| line.number).collect()
+    }
+}
+
+impl<'a> Iterator for Lines<'a> {
+    type Item = Line<'a>;
+
+    fn next(&mut self) -> Option<Line<'a>> {
+        loop {
+            let text = self.inner.next()?;
+            self.number += 1;
+            let line = Line{number: self.number, text};
+            if !(self.skip_blank && line.is_blank()) {
+                return Some(line);
+            }
+        }
+    }
+}
+
+// Works on raw text too, so that `Solver::parse` implementations can use it directly.
+// str::lines already drops the "\r" from "\r\n".
+pub fn lines(text: &str) -> Lines<'_> {
+    Lines{inner: text.trim_start_matches(BOM).lines(), number: 0, skip_blank: false}
+}
+
+#[test]
+fn test_clean() {
+    assert_eq!(clean("\u{feff}1\r\n2\r\n"), "1\n2\n");
+    assert_eq!(clean("1\n2"), "1\n2");
+    assert_eq!(Input::from_text("\u{feff}abc\r\n").text(), "abc\n");
+}
+
+#[test]
+fn test_lines() {
+    let numbered: Vec<(usize, &str)> = lines("\u{feff}a\r\n\r\n  \nb\r\n")
+        .map(|line| (line.number, line.text))
+        .collect();
+    assert_eq!(numbered, vec![(1, "a"), (2, ""), (3, "  "), (4, "b")]);
+
+    let numbered: Vec<(usize, &str)> = lines("a\r\n\r\n  \nb\r\n")
+        .skip_blank()
+        .map(|line| (line.number, line.text))
+        .collect();
+    assert_eq!(numbered, vec![(1, "a"), (4, "b")]);
+
+    assert_eq!(lines("a\n\nb\n \n").blank_line_numbers(), vec![2, 4]);
+    assert_eq!(lines("").count(), 0);
+}
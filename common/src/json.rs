@@ -0,0 +1,345 @@
+// A tiny JSON writer and reader, so that scripts can consume our answers without scraping Debug
+// output, and so that tests can share vector files with the Python solutions. Hand-rolled because
+// the build machines cannot fetch serde.
+
+use std::fmt;
+use std::fmt::Write;
+use std::str::FromStr;
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum Json {
+    Null,
+    Bool(bool),
+    // Kept as text so that integers of any width round-trip exactly.
+    Number(String),
+    String(String),
+    Array(Vec<Json>),
+    // A Vec rather than a map, so that fields come out in the order they were written.
+    Object(Vec<(String, Json)>),
+}
+
+impl Json {
+    // Builds an object from (name, value) pairs.
+    pub fn object(fields: Vec<(&str, Json)>) -> Json {
+        Json::Object(fields.into_iter().map(|(name, value)| (name.to_string(), value)).collect())
+    }
+
+    // Parses a single JSON value, surrounded by optional whitespace.
+    pub fn parse(text: &str) -> Result<Json, String> {
+        let mut parser = Parser{text, pos: 0};
+        let value = parser.parse_value()?;
+        parser.skip_whitespace();
+        if parser.pos < text.len() {
+            return Err(parser.error("trailing characters"));
+        }
+        Ok(value)
+    }
+
+    // Returns the value of the named field, or None if this is not an object or has no such
+    // field.
+    pub fn get(&self, name: &str) -> Option<&Json> {
+        match *self {
+            Json::Object(ref fields) => {
+                fields.iter().find(|field| field.0 == name).map(|field| &field.1)
+            },
+            _ => None,
+        }
+    }
+
+    pub fn as_array(&self) -> Option<&[Json]> {
+        match *self {
+            Json::Array(ref values) => Some(values),
+            _ => None,
+        }
+    }
+
+    pub fn as_bool(&self) -> Option<bool> {
+        match *self {
+            Json::Bool(b) => Some(b),
+            _ => None,
+        }
+    }
+
+    // Parses a number as any integer type. Returns None if this is not a number, or if the number
+    // does not fit.
+    pub fn as_int<T: FromStr>(&self) -> Option<T> {
+        match *self {
+            Json::Number(ref n) => n.parse().ok(),
+            _ => None,
+        }
+    }
+}
+
+struct Parser<'a> {
+    text: &'a str,
+    pos: usize,
+}
+
+impl<'a> Parser<'a> {
+    fn error(&self, message: &str) -> String {
+        format!("byte {}: {}", self.pos, message)
+    }
+
+    fn peek(&self) -> Option<u8> {
+        self.text.as_bytes().get(self.pos).copied()
+    }
+
+    fn skip_whitespace(&mut self) {
+        while let Some(b' ') | Some(b'\t') | Some(b'\n') | Some(b'\r') = self.peek() {
+            self.pos += 1;
+        }
+    }
+
+    fn expect(&mut self, c: u8) -> Result<(), String> {
+        self.skip_whitespace();
+        if self.peek() != Some(c) {
+            return Err(self.error(&format!("expected '{}'", c as char)));
+        }
+        self.pos += 1;
+        Ok(())
+    }
+
+    fn parse_value(&mut self) -> Result<Json, String> {
+        self.skip_whitespace();
+        match self.peek() {
+            Some(b'n') => self.parse_literal("null", Json::Null),
+            Some(b't') => self.parse_literal("true", Json::Bool(true)),
+            Some(b'f') => self.parse_literal("false", Json::Bool(false)),
+            Some(b'"') => Ok(Json::String(self.parse_string()?)),
+            Some(b'[') => {
+                self.pos += 1;
+                let mut values: Vec<Json> = Vec::new();
+                self.skip_whitespace();
+                if self.peek() == Some(b']') {
+                    self.pos += 1;
+                    return Ok(Json::Array(values));
+                }
+                loop {
+                    values.push(self.parse_value()?);
+                    self.skip_whitespace();
+                    if self.peek() == Some(b',') {
+                        self.pos += 1;
+                    } else {
+                        self.expect(b']')?;
+                        return Ok(Json::Array(values));
+                    }
+                }
+            },
+            Some(b'{') => {
+                self.pos += 1;
+                let mut fields: Vec<(String, Json)> = Vec::new();
+                self.skip_whitespace();
+                if self.peek() == Some(b'}') {
+                    self.pos += 1;
+                    return Ok(Json::Object(fields));
+                }
+                loop {
+                    self.skip_whitespace();
+                    let name = self.parse_string()?;
+                    self.expect(b':')?;
+                    fields.push((name, self.parse_value()?));
+                    self.skip_whitespace();
+                    if self.peek() == Some(b',') {
+                        self.pos += 1;
+                    } else {
+                        self.expect(b'}')?;
+                        return Ok(Json::Object(fields));
+                    }
+                }
+            },
+            Some(b'-') | Some(b'0'..=b'9') => {
+                let start = self.pos;
+                self.pos += 1;
+                while let Some(b'0'..=b'9') | Some(b'.') | Some(b'e') | Some(b'E') | Some(b'+')
+                        | Some(b'-') = self.peek() {
+                    self.pos += 1;
+                }
+                Ok(Json::Number(self.text[start..self.pos].to_string()))
+            },
+            Some(_) => Err(self.error("unexpected character")),
+            None => Err(self.error("unexpected end of input")),
+        }
+    }
+
+    fn parse_literal(&mut self, literal: &str, value: Json) -> Result<Json, String> {
+        if !self.text[self.pos..].starts_with(literal) {
+            return Err(self.error(&format!("expected {}", literal)));
+        }
+        self.pos += literal.len();
+        Ok(value)
+    }
+
+    fn parse_string(&mut self) -> Result<String, String> {
+        if self.peek() != Some(b'"') {
+            return Err(self.error("expected string"));
+        }
+        self.pos += 1;
+        let mut s = String::new();
+        let mut chars = self.text[self.pos..].char_indices();
+        while let Some((offset, c)) = chars.next() {
+            match c {
+                '"' => {
+                    self.pos += offset + 1;
+                    return Ok(s);
+                },
+                '\\' => {
+                    let escaped = match chars.next() {
+                        Some((_, '"')) => '"',
+                        Some((_, '\\')) => '\\',
+                        Some((_, '/')) => '/',
+                        Some((_, 'b')) => '\u{8}',
+                        Some((_, 'f')) => '\u{c}',
+                        Some((_, 'n')) => '\n',
+                        Some((_, 'r')) => '\r',
+                        Some((_, 't')) => '\t',
+                        Some((_, 'u')) => {
+                            let hex: String = (0..4).filter_map(|_| chars.next())
+                                .map(|(_, c)| c).collect();
+                            // Surrogate pairs are not supported; nothing we read needs them.
+                            u32::from_str_radix(&hex, 16).ok().and_then(char::from_u32)
+                                .ok_or_else(|| self.error("invalid \\u escape"))?
+                        },
+                        _ => return Err(self.error("invalid escape")),
+                    };
+                    s.push(escaped);
+                },
+                c => s.push(c),
+            }
+        }
+        Err(self.error("unterminated string"))
+    }
+}
+
+fn write_string(f: &mut fmt::Formatter, s: &str) -> fmt::Result {
+    f.write_char('"')?;
+    for c in s.chars() {
+        match c {
+            '"' => f.write_str("\\\"")?,
+            '\\' => f.write_str("\\\\")?,
+            '\n' => f.write_str("\\n")?,
+            '\r' => f.write_str("\\r")?,
+            '\t' => f.write_str("\\t")?,
+            c if (c as u32) < 0x20 => write!(f, "\\u{:04x}", c as u32)?,
+            c => f.write_char(c)?,
+        }
+    }
+    f.write_char('"')
+}
+
+// Writes compact JSON on a single line.
+impl fmt::Display for Json {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            Json::Null => f.write_str("null"),
+            Json::Bool(b) => write!(f, "{}", b),
+            Json::Number(ref n) => f.write_str(n),
+            Json::String(ref s) => write_string(f, s),
+            Json::Array(ref values) => {
+                f.write_char('[')?;
+                for (i, value) in values.iter().enumerate() {
+                    if i > 0 {
+                        f.write_char(',')?;
+                    }
+                    write!(f, "{}", value)?;
+                }
+                f.write_char(']')
+            },
+            Json::Object(ref fields) => {
+                f.write_char('{')?;
+                for (i, (name, value)) in fields.iter().enumerate() {
+                    if i > 0 {
+                        f.write_char(',')?;
+                    }
+                    write_string(f, name)?;
+                    write!(f, ":{}", value)?;
+                }
+                f.write_char('}')
+            },
+        }
+    }
+}
+
+pub trait ToJson {
+    fn to_json(&self) -> Json;
+}
+
+macro_rules! impl_to_json_for_integers {
+    ($($t:ty),*) => {
+        $(
+            impl ToJson for $t {
+                fn to_json(&self) -> Json {
+                    Json::Number(self.to_string())
+                }
+            }
+        )*
+    }
+}
+
+impl_to_json_for_integers!(i8, i16, i32, i64, i128, isize, u8, u16, u32, u64, u128, usize);
+
+impl ToJson for bool {
+    fn to_json(&self) -> Json {
+        Json::Bool(*self)
+    }
+}
+
+impl ToJson for char {
+    fn to_json(&self) -> Json {
+        Json::String(self.to_string())
+    }
+}
+
+impl ToJson for String {
+    fn to_json(&self) -> Json {
+        Json::String(self.clone())
+    }
+}
+
+impl ToJson for () {
+    fn to_json(&self) -> Json {
+        Json::Null
+    }
+}
+
+impl<T: ToJson> ToJson for Option<T> {
+    fn to_json(&self) -> Json {
+        match *self {
+            Some(ref value) => value.to_json(),
+            None => Json::Null,
+        }
+    }
+}
+
+impl<T: ToJson> ToJson for Vec<T> {
+    fn to_json(&self) -> Json {
+        Json::Array(self.iter().map(|value| value.to_json()).collect())
+    }
+}
+
+#[test]
+fn test_to_string() {
+    assert_eq!(Json::Null.to_string(), "null");
+    assert_eq!((-12i32).to_json().to_string(), "-12");
+    assert_eq!(Some(vec![1u8, 2]).to_json().to_string(), "[1,2]");
+    assert_eq!(None::<i32>.to_json().to_string(), "null");
+    assert_eq!("a\"b\\c\n\u{1}".to_string().to_json().to_string(), "\"a\\\"b\\\\c\\n\\u0001\"");
+    let object = Json::object(vec![("char_removed", 'v'.to_json()), ("length", 4944.to_json())]);
+    assert_eq!(object.to_string(), "{\"char_removed\":\"v\",\"length\":4944}");
+}
+
+#[test]
+fn test_parse() {
+    assert_eq!(Json::parse(" null "), Ok(Json::Null));
+    assert_eq!(Json::parse("[true, false, -12, []]"),
+               Ok(Json::Array(vec![Json::Bool(true), Json::Bool(false),
+                                   Json::Number("-12".to_string()), Json::Array(vec![])])));
+    let text = "{\"char_removed\":\"v\",\"length\":4944}";
+    let object = Json::parse(text).unwrap();
+    assert_eq!(object.to_string(), text);
+    assert_eq!(object.get("length").and_then(|n| n.as_int::<i32>()), Some(4944));
+    assert_eq!(object.get("missing"), None);
+    assert_eq!(Json::parse("\"a\\\"b\\\\c\\n\\u0041\""),
+               Ok(Json::String("a\"b\\c\nA".to_string())));
+    assert_eq!(Json::parse("[1, 2"), Err("byte 5: expected ']'".to_string()));
+    assert_eq!(Json::parse("{} x"), Err("byte 3: trailing characters".to_string()));
+}
@@ -0,0 +1,17 @@
+// Code shared by all days.
+
+pub mod budget;
+pub mod input;
+pub mod json;
+pub mod parse;
+pub mod property;
+pub mod rng;
+mod solver;
+
+pub use budget::Budget;
+pub use json::Json;
+pub use json::ToJson;
+pub use parse::ParseError;
+pub use solver::Solver;
+pub use solver::SolverExtras;
+pub use solver::Variant;
@@ -0,0 +1,142 @@
+// Parse errors that point at the offending line and bytes, so that a bad input can be fixed in
+// one go instead of one panic at a time.
+
+use std::error::Error;
+use std::fmt;
+use std::num::IntErrorKind;
+use std::num::ParseIntError;
+use std::ops::Range;
+use std::str::FromStr;
+
+use input;
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum ParseErrorKind {
+    // The text does not look like anything we know how to parse.
+    UnknownFormat,
+    // The text is an integer, but it does not fit in the target type.
+    IntegerOverflow,
+    // The line was split into the wrong number of fields.
+    WrongFieldCount { expected: usize, found: usize },
+    // Nothing between two separators, e.g. the middle of "1,,2".
+    EmptyToken,
+    // A sign that could belong to more than one number, e.g. "1-2" or a lone "+".
+    AmbiguousSign,
+    // A line that only makes sense after some other line, e.g. day 04's "falls asleep" before any
+    // guard begins a shift.
+    MissingEarlier { what: &'static str },
+    // A line that cannot follow the line before it, e.g. day 04's "falls asleep" right after
+    // another "falls asleep".
+    Unexpected { after: &'static str },
+}
+
+impl fmt::Display for ParseErrorKind {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            ParseErrorKind::UnknownFormat => write!(f, "unknown format"),
+            ParseErrorKind::IntegerOverflow => write!(f, "integer overflow"),
+            ParseErrorKind::WrongFieldCount{expected, found} => {
+                write!(f, "expected {} fields, found {}", expected, found)
+            },
+            ParseErrorKind::EmptyToken => write!(f, "empty token"),
+            ParseErrorKind::AmbiguousSign => write!(f, "ambiguous sign"),
+            ParseErrorKind::MissingEarlier{what} => write!(f, "no earlier {}", what),
+            ParseErrorKind::Unexpected{after} => write!(f, "unexpected right after {}", after),
+        }
+    }
+}
+
+// Interpretation: line number `line` (1-based) contains `text` at bytes `span`, and that text
+// could not be parsed for reason `kind`.
+#[derive(Clone, Debug, PartialEq)]
+pub struct ParseError {
+    pub kind: ParseErrorKind,
+    pub line: usize,
+    pub text: String,
+    pub span: Range<usize>,
+}
+
+impl ParseError {
+    // Creates an error for the bytes `span` of `line_text`. The line number defaults to 1, since
+    // a single string is a one-line input; use at_line() to fix it up.
+    pub fn new(kind: ParseErrorKind, line_text: &str, span: Range<usize>) -> ParseError {
+        ParseError{kind, line: 1, text: line_text[span.clone()].to_string(), span}
+    }
+
+    pub fn at_line(mut self, line: usize) -> ParseError {
+        self.line = line;
+        self
+    }
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "line {}, bytes {}..{}: {}: {:?}",
+               self.line, self.span.start, self.span.end, self.kind, self.text)
+    }
+}
+
+impl Error for ParseError {}
+
+// Returns the span of `line_text` without leading and trailing whitespace.
+pub fn trimmed_span(line_text: &str) -> Range<usize> {
+    let start = line_text.len() - line_text.trim_start().len();
+    let end = line_text.trim_end().len();
+    if start > end { end..end } else { start..end }
+}
+
+// Parses the integer at bytes `span` of `line_text`.
+pub fn parse_int<T>(line_text: &str, span: Range<usize>) -> Result<T, ParseError>
+        where T: FromStr<Err = ParseIntError> {
+    line_text[span.clone()].parse().map_err(|e: ParseIntError| {
+        let kind = match *e.kind() {
+            IntErrorKind::PosOverflow | IntErrorKind::NegOverflow => {
+                ParseErrorKind::IntegerOverflow
+            },
+            _ => ParseErrorKind::UnknownFormat,
+        };
+        ParseError::new(kind, line_text, span)
+    })
+}
+
+// Parses every non-blank line of `input` with `parse_line`. Returns all the errors, not just the
+// first one.
+pub fn parse_lines<T, F>(input: &str, parse_line: F) -> Result<Vec<T>, Vec<ParseError>>
+        where F: Fn(&str) -> Result<T, ParseError> {
+    let mut values: Vec<T> = Vec::new();
+    let mut errors: Vec<ParseError> = Vec::new();
+    for line in input::lines(input).skip_blank() {
+        match parse_line(line.text) {
+            Ok(value) => values.push(value),
+            Err(error) => errors.push(error.at_line(line.number)),
+        }
+    }
+    if errors.is_empty() {
+        Ok(values)
+    } else {
+        Err(errors)
+    }
+}
+
+#[test]
+fn test_parse_int() {
+    assert_eq!(parse_int::<i32>("  +12 ", 2..5), Ok(12));
+    assert_eq!(parse_int::<i32>("x99999999999", 1..12),
+               Err(ParseError{kind: ParseErrorKind::IntegerOverflow, line: 1,
+                              text: "99999999999".to_string(), span: 1..12}));
+    assert_eq!(parse_int::<i32>("1a", 0..2).unwrap_err().kind, ParseErrorKind::UnknownFormat);
+}
+
+#[test]
+fn test_parse_lines() {
+    let parse_line = |text: &str| parse_int::<i8>(text, trimmed_span(text));
+    assert_eq!(parse_lines("1\n\n 2\n", parse_line), Ok(vec![1, 2]));
+    let errors = parse_lines("1\nfoo\n3\n 300\n", parse_line).unwrap_err();
+    assert_eq!(errors, vec![
+        ParseError{kind: ParseErrorKind::UnknownFormat, line: 2,
+                   text: "foo".to_string(), span: 0..3},
+        ParseError{kind: ParseErrorKind::IntegerOverflow, line: 4,
+                   text: "300".to_string(), span: 1..4},
+    ]);
+    assert_eq!(errors[1].to_string(), "line 4, bytes 1..4: integer overflow: \"300\"");
+}
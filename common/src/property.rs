@@ -0,0 +1,123 @@
+// A minimal property-testing helper: run a property on many random inputs, and when it fails,
+// shrink the input to a minimal counterexample. Std only, since the build machines cannot fetch
+// proptest or quickcheck.
+
+use std::fmt::Debug;
+
+use rng::Rng;
+
+// Runs `property` on `num_cases` inputs from `generate`. If it fails, repeatedly replaces the
+// failing input with the first candidate from `shrink` that still fails, until none do. Returns
+// the minimal failing input and its error message, or None if every case passed.
+pub fn find_counterexample<T, G, S, P>(seed: u64, num_cases: usize, generate: G, shrink: S,
+                                       property: P) -> Option<(T, String)>
+        where G: Fn(&mut Rng) -> T, S: Fn(&T) -> Vec<T>, P: Fn(&T) -> Result<(), String> {
+    let mut rng = Rng::new(seed);
+    for _ in 0..num_cases {
+        let input = generate(&mut rng);
+        if let Err(message) = property(&input) {
+            return Some(shrink_failure(input, message, &shrink, &property));
+        }
+    }
+    None
+}
+
+fn shrink_failure<T, S, P>(mut input: T, mut message: String, shrink: &S, property: &P)
+        -> (T, String)
+        where S: Fn(&T) -> Vec<T>, P: Fn(&T) -> Result<(), String> {
+    'outer: loop {
+        for candidate in shrink(&input) {
+            if let Err(candidate_message) = property(&candidate) {
+                input = candidate;
+                message = candidate_message;
+                continue 'outer;
+            }
+        }
+        return (input, message);
+    }
+}
+
+// Like find_counterexample(), but panics with the minimal counterexample.
+pub fn check<T, G, S, P>(seed: u64, num_cases: usize, generate: G, shrink: S, property: P)
+        where T: Debug, G: Fn(&mut Rng) -> T, S: Fn(&T) -> Vec<T>,
+              P: Fn(&T) -> Result<(), String> {
+    if let Some((input, message)) = find_counterexample(seed, num_cases, generate, shrink,
+                                                        property) {
+        panic!("property failed for {:?}: {}", input, message);
+    }
+}
+
+// Candidates closer to zero: 0, the positive version of a negative number, halfway, and one step
+// closer.
+pub fn shrink_i64(x: i64) -> Vec<i64> {
+    let mut candidates: Vec<i64> = Vec::new();
+    if x == 0 {
+        return candidates;
+    }
+    candidates.push(0);
+    if x < 0 && x != i64::MIN {
+        candidates.push(-x);
+    }
+    for candidate in [x / 2, x - x.signum()] {
+        if !candidates.contains(&candidate) && candidate != x {
+            candidates.push(candidate);
+        }
+    }
+    candidates
+}
+
+// Candidates that drop chunks of elements (halves first, then single elements), then candidates
+// that shrink one element with `shrink_elem`.
+pub fn shrink_vec<T, S>(v: &[T], shrink_elem: S) -> Vec<Vec<T>>
+        where T: Clone, S: Fn(&T) -> Vec<T> {
+    let mut candidates: Vec<Vec<T>> = Vec::new();
+    let mut chunk_len = v.len() / 2;
+    while chunk_len > 0 {
+        let mut start = 0;
+        while start < v.len() {
+            let end = (start + chunk_len).min(v.len());
+            let mut candidate = v[..start].to_vec();
+            candidate.extend_from_slice(&v[end..]);
+            candidates.push(candidate);
+            start = end;
+        }
+        chunk_len /= 2;
+    }
+    for (i, elem) in v.iter().enumerate() {
+        for smaller in shrink_elem(elem) {
+            let mut candidate = v.to_vec();
+            candidate[i] = smaller;
+            candidates.push(candidate);
+        }
+    }
+    candidates
+}
+
+#[test]
+fn test_shrink_i64() {
+    assert_eq!(shrink_i64(0), vec![]);
+    assert_eq!(shrink_i64(1), vec![0]);
+    assert_eq!(shrink_i64(10), vec![0, 5, 9]);
+    assert_eq!(shrink_i64(-10), vec![0, 10, -5, -9]);
+}
+
+#[test]
+fn test_find_counterexample() {
+    let generate = |rng: &mut Rng| -> Vec<i64> {
+        (0..rng.range(0, 20)).map(|_| rng.range(-100, 100)).collect()
+    };
+    let shrink = |v: &Vec<i64>| shrink_vec(v, |&x| shrink_i64(x));
+
+    let passes = |v: &Vec<i64>| if v.len() <= 20 { Ok(()) } else { Err("too long".to_string()) };
+    assert_eq!(find_counterexample(1, 100, generate, shrink, passes), None);
+
+    // Shrinking stops when every element is positive and the sum is exactly 100, because then
+    // dropping or decrementing any element makes the property pass.
+    let fails = |v: &Vec<i64>| {
+        let sum: i64 = v.iter().sum();
+        if sum < 100 { Ok(()) } else { Err(format!("sum is {}", sum)) }
+    };
+    let (input, message) = find_counterexample(1, 1000, generate, shrink, fails).unwrap();
+    assert!(input.iter().all(|&x| x > 0), "{:?}", input);
+    assert_eq!(message, "sum is 100");
+}
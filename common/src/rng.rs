@@ -0,0 +1,88 @@
+// A small seeded random number generator (SplitMix64), for generating test inputs. Not
+// cryptographically secure, and not meant to be. Std only, since the build machines cannot fetch
+// the rand crate.
+
+pub struct Rng {
+    state: u64,
+}
+
+impl Rng {
+    pub fn new(seed: u64) -> Rng {
+        Rng{state: seed}
+    }
+
+    pub fn next_u64(&mut self) -> u64 {
+        self.state = self.state.wrapping_add(0x9e37_79b9_7f4a_7c15);
+        let mut z = self.state;
+        z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+        z ^ (z >> 31)
+    }
+
+    // Returns a number in 0..n. Panics if n == 0.
+    pub fn below(&mut self, n: u64) -> u64 {
+        assert!(n > 0);
+        // Multiply-shift instead of %, so that the result is not biased towards small numbers.
+        ((u128::from(self.next_u64()) * u128::from(n)) >> 64) as u64
+    }
+
+    // Returns a number in lo..=hi.
+    pub fn range(&mut self, lo: i64, hi: i64) -> i64 {
+        assert!(lo <= hi);
+        let width = (hi as i128 - lo as i128 + 1) as u128;
+        if width > u128::from(u64::MAX) {
+            return self.next_u64() as i64;
+        }
+        (lo as i128 + i128::from(self.below(width as u64))) as i64
+    }
+
+    // Returns true with probability num / den.
+    pub fn chance(&mut self, num: u64, den: u64) -> bool {
+        self.below(den) < num
+    }
+
+    pub fn choose<'a, T>(&mut self, items: &'a [T]) -> &'a T {
+        &items[self.below(items.len() as u64) as usize]
+    }
+
+    // Fisher-Yates.
+    pub fn shuffle<T>(&mut self, items: &mut [T]) {
+        for i in (1..items.len()).rev() {
+            let j = self.below(i as u64 + 1) as usize;
+            items.swap(i, j);
+        }
+    }
+}
+
+#[test]
+fn test_deterministic() {
+    let a: Vec<u64> = { let mut rng = Rng::new(42); (0..5).map(|_| rng.next_u64()).collect() };
+    let b: Vec<u64> = { let mut rng = Rng::new(42); (0..5).map(|_| rng.next_u64()).collect() };
+    let c: Vec<u64> = { let mut rng = Rng::new(43); (0..5).map(|_| rng.next_u64()).collect() };
+    assert_eq!(a, b);
+    assert!(a != c);
+}
+
+#[test]
+fn test_range() {
+    let mut rng = Rng::new(1);
+    let mut seen = [false; 7];
+    for _ in 0..1000 {
+        let x = rng.range(-3, 3);
+        assert!((-3..=3).contains(&x));
+        seen[(x + 3) as usize] = true;
+    }
+    assert!(seen.iter().all(|&s| s));
+    assert_eq!(rng.range(5, 5), 5);
+    rng.range(i64::MIN, i64::MAX);
+}
+
+#[test]
+fn test_shuffle() {
+    let mut rng = Rng::new(7);
+    let mut items: Vec<u32> = (0..100).collect();
+    rng.shuffle(&mut items);
+    assert!(items != (0..100).collect::<Vec<u32>>());
+    items.sort_unstable();
+    assert_eq!(items, (0..100).collect::<Vec<u32>>());
+}
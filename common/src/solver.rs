@@ -0,0 +1,68 @@
+use std::fmt::Debug;
+use std::hint::black_box;
+use std::io::BufRead;
+
+use budget::Budget;
+use json::Json;
+use json::ToJson;
+use parse::ParseError;
+
+// One implementation of one part, e.g. day 01's solve_part2_naive. `run` throws away the answer
+// (through black_box, so the work is not optimized out); it exists for timing.
+pub struct Variant<I> {
+    pub name: &'static str,
+    pub part: u32,
+    pub run: fn(&I),
+}
+
+// The common shape of every day: parse the input into a domain type, then solve each part.
+// Implemented by a unit struct in each day's crate, e.g. `day01::Day01`.
+pub trait Solver {
+    type Input;
+    type Output1: Debug + ToJson;
+    type Output2: Debug + ToJson;
+
+    // False for days whose part 2 has not been solved yet.
+    const HAS_PART2: bool = true;
+
+    // Returns every bad line, not just the first one.
+    fn parse(input: &str) -> Result<Self::Input, Vec<ParseError>>;
+    // Parts return an error for inputs that parse but cannot be solved, e.g. day 01's frequency
+    // overflowing.
+    fn part1(input: &Self::Input) -> Result<Self::Output1, String>;
+    fn part2(input: &Self::Input) -> Result<Self::Output2, String>;
+
+    // Every implementation worth timing. Days with competing implementations (naive vs fast)
+    // override this to list them all.
+    fn variants() -> Vec<Variant<Self::Input>> {
+        let mut variants = vec![
+            Variant{name: "part1", part: 1,
+                    run: |input| { let _ = black_box(Self::part1(input)); }}];
+        if Self::HAS_PART2 {
+            variants.push(
+                Variant{name: "part2", part: 2,
+                        run: |input| { let _ = black_box(Self::part2(input)); }});
+        }
+        variants
+    }
+}
+
+// What only some days have, on top of Solver: so far, only day 01. aoc finds out which days
+// implement it through days::dispatch_extras(), and rejects the flags that need it for other days
+// before running anything.
+pub trait SolverExtras: Solver {
+    // A human-readable derivation of the part 2 answer, for `aoc run --explain`.
+    fn explain(input: &Self::Input) -> Result<String, String>;
+
+    // Numbers that describe the input, for `aoc stats`, as a JSON object.
+    fn stats(input: &Self::Input) -> Result<Json, String>;
+
+    // Part 2 by the day's slow but obviously correct solver, within `budget`, for `aoc run
+    // --naive`. Returns Ok(None) if the budget runs out.
+    fn part2_reference(input: &Self::Input, budget: &Budget)
+            -> Result<Option<Self::Output2>, String>;
+
+    // Solves both parts reading the input a line at a time, for inputs too big to read into
+    // memory (see `aoc run --stream`).
+    fn solve_stream(reader: &mut dyn BufRead) -> Result<(Self::Output1, Self::Output2), String>;
+}